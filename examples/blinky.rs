@@ -9,9 +9,7 @@ use stm32g0_ll_drivers::{gpio, rcc};
 fn main() -> ! {
     let mut rcc = rcc::Rcc::take().unwrap();
 
-    rcc.enable_gpio_port_clock(rcc::GPIOPort::GPIOA);
-
-    let pins = gpio::gpioa::Pins::take();
+    let pins = gpio::gpioa::Pins::take(&mut rcc).unwrap();
     let mut led = pins.pa5.into_output_push_pull();
 
     loop {
@@ -0,0 +1,28 @@
+#![no_std]
+#![no_main]
+
+use panic_halt as _;
+
+use core::fmt::Write;
+use stm32g0_ll_drivers::{gpio, pac, rcc, serial};
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    let mut rcc = rcc::Rcc::take().unwrap();
+
+    let pins = gpio::gpioa::Pins::take(&mut rcc).unwrap();
+    let tx = pins.pa2.into_alternate::<1>();
+    let rx = pins.pa3.into_alternate::<1>();
+
+    let mut serial =
+        serial::Serial::<pac::USART2, _>::new(&mut rcc, (tx, rx), serial::Config::default())
+            .unwrap();
+
+    writeln!(serial, "Echo ready").unwrap();
+
+    loop {
+        if let Ok(word) = serial.read() {
+            serial.write(word);
+        }
+    }
+}
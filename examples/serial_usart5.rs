@@ -0,0 +1,29 @@
+#![no_std]
+#![no_main]
+
+use panic_halt as _;
+
+use core::fmt::Write;
+use stm32g0_ll_drivers::{gpio, pac, rcc, serial};
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    let mut rcc = rcc::Rcc::take().unwrap();
+
+    // USART5 is on AF8, only available on the STM32G0B1/G0C1
+    let pins = gpio::gpiob::Pins::take(&mut rcc).unwrap();
+    let tx = pins.pb0.into_alternate::<8>();
+    let rx = pins.pb1.into_alternate::<8>();
+
+    let mut serial =
+        serial::Serial::<pac::USART5, _>::new(&mut rcc, (tx, rx), serial::Config::default())
+            .unwrap();
+
+    writeln!(serial, "Echo ready").unwrap();
+
+    loop {
+        if let Ok(word) = serial.read() {
+            serial.write(word);
+        }
+    }
+}
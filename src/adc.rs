@@ -4,72 +4,100 @@ use core::sync::atomic::{AtomicBool, Ordering};
 
 static TAKEN: AtomicBool = AtomicBool::new(false);
 
-pub struct Adc<'a> {
-    rb: &'a pac::adc::RegisterBlock,
+pub struct Adc {
+    rb: *const pac::adc::RegisterBlock,
 }
 
-impl Adc<'static> {
-    pub fn new(rcc: &mut rcc::Rcc<'static>) -> Option<Self> {
+impl Adc {
+    pub fn new(rcc: &mut rcc::Rcc) -> Option<Self> {
         // Enable the ADC peripheral clock
         rcc.enable_peripheral_clock(rcc::Peripheral::APB2(rcc::APB2Peripheral::ADC));
 
-        unsafe {
-            if TAKEN.load(Ordering::Relaxed) {
-                None
-            } else {
-                TAKEN.store(true, Ordering::Relaxed);
-
-                Some(Self {
-                    rb: &*pac::ADC::ptr(),
-                })
-            }
+        if TAKEN.load(Ordering::Relaxed) {
+            None
+        } else {
+            TAKEN.store(true, Ordering::Relaxed);
+
+            Some(Self {
+                rb: pac::ADC::ptr(),
+            })
         }
     }
 
     ///  Set ADC clock mode
     pub fn set_clock_mode(&mut self, clock_mode: ClockMode) {
-        self.rb
-            .cfgr2
-            .modify(|_, w| w.ckmode().bits(clock_mode.into()));
+        unsafe {
+            (*self.rb)
+                .cfgr2()
+                .modify(|_, w| w.ckmode().bits(clock_mode.into()));
+        }
     }
 
     /// Get ADC clock mode
     pub fn get_clock_mode(&mut self) -> Option<ClockMode> {
-        ClockMode::from_u8(self.rb.cfgr2.read().ckmode().bits())
+        unsafe { ClockMode::from_u8((*self.rb).cfgr2().read().ckmode().bits()) }
+    }
+
+    /// Get ADC clock frequency, after the clock mode divider or the
+    /// asynchronous clock prescaler
+    pub fn get_clock_frequency(&mut self, rcc: &mut rcc::Rcc) -> u32 {
+        match self.get_clock_mode() {
+            Some(ClockMode::SyncPclkDiv1) => rcc.get_pclk_frequency(),
+            Some(ClockMode::SyncPclkDiv2) => rcc.get_pclk_frequency() / 2,
+            Some(ClockMode::SyncPclkDiv4) => rcc.get_pclk_frequency() / 4,
+            _ => {
+                let divider = match unsafe { (*self.rb).ccr().read().presc().bits() } {
+                    presc @ 0..=2 => 1 << presc,
+                    presc @ 3..=6 => 2 * presc as u32,
+                    presc @ 7..=11 => 1 << (presc - 3),
+                    _ => 256,
+                };
+
+                rcc.get_adc_clock_frequency() / divider
+            }
+        }
     }
 
     /// Set ADC resolution
     pub fn set_resolution(&mut self, resolution: Resolution) {
-        self.rb.cfgr1.modify(|_, w| w.res().bits(resolution.into()));
+        unsafe {
+            (*self.rb)
+                .cfgr1()
+                .modify(|_, w| w.res().bits(resolution.into()));
+        }
     }
 
     /// Get ADC resolution
     pub fn get_resolution(&mut self) -> Resolution {
-        Resolution::from_u8(self.rb.cfgr1.read().res().bits()).unwrap()
+        unsafe { Resolution::from_u8((*self.rb).cfgr1().read().res().bits()).unwrap() }
     }
 
     /// Set ADC data alignment
     pub fn set_data_alignment(&mut self, data_alignment: DataAlignment) {
-        self.rb
-            .cfgr1
-            .modify(|_, w| w.align().bit(data_alignment.into()));
+        unsafe {
+            (*self.rb)
+                .cfgr1()
+                .modify(|_, w| w.align().bit(data_alignment.into()));
+        }
     }
 
     /// Get ADC data alignment
     pub fn get_data_alignment(&mut self) -> DataAlignment {
-        DataAlignment::from_bool(self.rb.cfgr1.read().align().bit())
+        unsafe { DataAlignment::from_bool((*self.rb).cfgr1().read().align().bit()) }
     }
 
     /// Set ADC low power mode
     pub fn set_low_power_mode(&mut self, low_power_mode: LowPowerMode) {
-        self.rb.cfgr1.modify(|r, w| unsafe {
-            w.bits(r.bits() & !((u8::from(low_power_mode) as u32) << 14))
-        });
+        unsafe {
+            (*self.rb)
+                .cfgr1()
+                .modify(|r, w| w.bits(r.bits() & !((u8::from(low_power_mode) as u32) << 14)));
+        }
     }
 
     /// Get ADC low power mode
     pub fn get_low_power_mode(&mut self) -> Option<LowPowerMode> {
-        LowPowerMode::from_u8(((self.rb.cfgr1.read().bits() >> 14) & 3u32) as u8)
+        unsafe { LowPowerMode::from_u8((((*self.rb).cfgr1().read().bits() >> 14) & 3u32) as u8) }
     }
 
     /// Set sampling time for a common group
@@ -78,9 +106,11 @@ impl Adc<'static> {
         common_group: SamplingTimeCommonGroup,
         sampling_time: SamplingTime,
     ) {
-        self.rb.smpr.modify(|r, w| unsafe {
-            w.bits(r.bits() & !((u8::from(sampling_time) << u8::from(common_group)) as u32))
-        });
+        unsafe {
+            (*self.rb).smpr().modify(|r, w| {
+                w.bits(r.bits() & !((u8::from(sampling_time) << u8::from(common_group)) as u32))
+            });
+        }
     }
 
     /// Get sampling time of a common group
@@ -88,8 +118,12 @@ impl Adc<'static> {
         &mut self,
         common_group: SamplingTimeCommonGroup,
     ) -> SamplingTime {
-        SamplingTime::from_u8(((self.rb.smpr.read().bits() >> u8::from(common_group)) & 7u32) as u8)
+        unsafe {
+            SamplingTime::from_u8(
+                (((*self.rb).smpr().read().bits() >> u8::from(common_group)) & 7u32) as u8,
+            )
             .unwrap()
+        }
     }
 
     /// Set sampling time group for a channel
@@ -98,16 +132,23 @@ impl Adc<'static> {
         channel: Channel,
         common_group: SamplingTimeCommonGroup,
     ) {
-        self.rb.smpr.modify(|r, w| unsafe {
-            w.bits(r.bits() & !((bool::from(common_group) as u8) << (u8::from(channel) + 8)) as u32)
-        });
+        unsafe {
+            (*self.rb).smpr().modify(|r, w| {
+                w.bits(
+                    r.bits()
+                        & !((bool::from(common_group) as u8) << (u8::from(channel) + 8)) as u32,
+                )
+            });
+        }
     }
 
     /// Get sampling time group of a channel
     pub fn get_channel_sampling_time_group(&mut self, channel: Channel) -> SamplingTimeCommonGroup {
-        SamplingTimeCommonGroup::from_bool(
-            ((self.rb.smpr.read().bits() >> (u8::from(channel) + 8)) & 1u32) != 0,
-        )
+        unsafe {
+            SamplingTimeCommonGroup::from_bool(
+                (((*self.rb).smpr().read().bits() >> (u8::from(channel) + 8)) & 1u32) != 0,
+            )
+        }
     }
 }
 
@@ -128,10 +169,10 @@ impl From<ClockMode> for u8 {
     fn from(value: ClockMode) -> Self {
         use ClockMode::*;
         match value {
-            SyncPclkDiv1 => 0,
+            Async => 0,
             SyncPclkDiv2 => 1,
             SyncPclkDiv4 => 2,
-            Async => 3,
+            SyncPclkDiv1 => 3,
         }
     }
 }
@@ -141,10 +182,10 @@ impl ClockMode {
     pub fn from_u8(value: u8) -> Option<Self> {
         use ClockMode::*;
         match value {
-            0 => Some(SyncPclkDiv1),
+            0 => Some(Async),
             1 => Some(SyncPclkDiv2),
             2 => Some(SyncPclkDiv4),
-            3 => Some(Async),
+            3 => Some(SyncPclkDiv1),
             _ => None,
         }
     }
@@ -187,6 +228,22 @@ impl Resolution {
             _ => None,
         }
     }
+
+    /// Number of bits of the conversion result
+    pub fn bits(self) -> u8 {
+        use Resolution::*;
+        match self {
+            Bits12 => 12,
+            Bits10 => 10,
+            Bits8 => 8,
+            Bits6 => 6,
+        }
+    }
+
+    /// Successive approximation time in half ADC clock cycles
+    fn conversion_half_cycles(self) -> u32 {
+        u32::from(self.bits()) * 2 + 1
+    }
 }
 
 /// ADC data alignment
@@ -345,6 +402,147 @@ impl SamplingTime {
             _ => None,
         }
     }
+
+    /// Sampling time in half ADC clock cycles
+    fn half_cycles(self) -> u32 {
+        use SamplingTime::*;
+        match self {
+            T1_5 => 3,
+            T3_5 => 7,
+            T7_5 => 15,
+            T12_5 => 25,
+            T19_5 => 39,
+            T39_5 => 79,
+            T79_5 => 159,
+            T160_5 => 321,
+        }
+    }
+}
+
+/// ADC oversampling ratio
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OversamplingRatio {
+    /// Oversampling disabled
+    Disabled,
+    /// 2x oversampling
+    X2,
+    /// 4x oversampling
+    X4,
+    /// 8x oversampling
+    X8,
+    /// 16x oversampling
+    X16,
+    /// 32x oversampling
+    X32,
+    /// 64x oversampling
+    X64,
+    /// 128x oversampling
+    X128,
+    /// 256x oversampling
+    X256,
+}
+
+impl OversamplingRatio {
+    /// Number of conversions accumulated into one result
+    pub fn conversions(self) -> u32 {
+        use OversamplingRatio::*;
+        match self {
+            Disabled => 1,
+            X2 => 2,
+            X4 => 4,
+            X8 => 8,
+            X16 => 16,
+            X32 => 32,
+            X64 => 64,
+            X128 => 128,
+            X256 => 256,
+        }
+    }
+}
+
+/// ADC sample and hold capacitance (C_ADC), in farads
+const SAMPLING_CAPACITANCE: f32 = 5e-12;
+
+/// ADC sampling switch resistance (R_ADC, worst case), in ohms
+const SAMPLING_SWITCH_RESISTANCE: f32 = 2_200.0;
+
+/// ADC conversion timing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConversionTiming {
+    /// Time to get one result, in nanoseconds (saturates at `u32::MAX`)
+    pub conversion_time_ns: u32,
+    /// Maximum sample rate, in samples per second
+    pub max_sample_rate: u32,
+}
+
+/// Compute the conversion time and maximum sample rate
+///
+/// `adc_clock_hz` is the ADC clock, as returned by `Adc::get_clock_frequency`.
+/// Returns `None` if it is zero.
+///
+/// ```rust
+/// use stm32g0_ll_drivers::adc;
+///
+/// // 12 bits, 12.5 cycles sampling time at 16MHz: 25 cycles per conversion
+/// let timing = adc::conversion_timing(
+///     16_000_000,
+///     adc::SamplingTime::T12_5,
+///     adc::Resolution::Bits12,
+///     adc::OversamplingRatio::Disabled,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(timing.conversion_time_ns, 1_562);
+/// assert_eq!(timing.max_sample_rate, 640_000);
+/// ```
+pub fn conversion_timing(
+    adc_clock_hz: u32,
+    sampling_time: SamplingTime,
+    resolution: Resolution,
+    oversampling: OversamplingRatio,
+) -> Option<ConversionTiming> {
+    if adc_clock_hz == 0 {
+        return None;
+    }
+
+    let half_cycles = u64::from(sampling_time.half_cycles() + resolution.conversion_half_cycles())
+        * u64::from(oversampling.conversions());
+    let half_cycles_per_second = 2 * u64::from(adc_clock_hz);
+
+    Some(ConversionTiming {
+        conversion_time_ns: u32::try_from(half_cycles * 1_000_000_000 / half_cycles_per_second)
+            .unwrap_or(u32::MAX),
+        max_sample_rate: (half_cycles_per_second / half_cycles) as u32,
+    })
+}
+
+/// Get the minimum sampling time needed to charge the sample and hold
+/// capacitor through a source impedance to within half an LSB
+///
+/// Returns `None` if even the longest sampling time is too short.
+///
+/// ```rust
+/// use stm32g0_ll_drivers::adc;
+///
+/// let sampling_time = adc::min_sampling_time(16_000_000, adc::Resolution::Bits12, 10_000);
+///
+/// assert_eq!(sampling_time, Some(adc::SamplingTime::T12_5));
+/// ```
+pub fn min_sampling_time(
+    adc_clock_hz: u32,
+    resolution: Resolution,
+    source_impedance_ohm: u32,
+) -> Option<SamplingTime> {
+    // t_s >= (R_AIN + R_ADC) * C_ADC * ln(2^(N + 2))
+    let sampling_time_s = (source_impedance_ohm as f32 + SAMPLING_SWITCH_RESISTANCE)
+        * SAMPLING_CAPACITANCE
+        * (f32::from(resolution.bits()) + 2.0)
+        * core::f32::consts::LN_2;
+    let half_cycles = sampling_time_s * 2.0 * adc_clock_hz as f32;
+
+    (0..8)
+        .filter_map(SamplingTime::from_u8)
+        .find(|t| t.half_cycles() as f32 >= half_cycles)
 }
 
 /// ADC channel
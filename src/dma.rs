@@ -0,0 +1,353 @@
+//! DMA channels, with their requests routed by the DMAMUX
+//!
+//! Channels are numbered after their DMAMUX channel: 1 to 7 are the DMA1
+//! channels and 8 to 12 are the DMA2 channels 1 to 5.
+
+use crate::{pac, rcc};
+use core::convert::From;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// DMA channel, with its number known at compile time
+pub struct Channel<const N: u8> {
+    _private: (),
+}
+
+pub struct Channels {
+    pub ch1: Channel<1>,
+    pub ch2: Channel<2>,
+    pub ch3: Channel<3>,
+    pub ch4: Channel<4>,
+    pub ch5: Channel<5>,
+    #[cfg(not(any(feature = "stm32g030", feature = "stm32g031", feature = "stm32g041")))]
+    pub ch6: Channel<6>,
+    #[cfg(not(any(feature = "stm32g030", feature = "stm32g031", feature = "stm32g041")))]
+    pub ch7: Channel<7>,
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+    pub ch8: Channel<8>,
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+    pub ch9: Channel<9>,
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+    pub ch10: Channel<10>,
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+    pub ch11: Channel<11>,
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+    pub ch12: Channel<12>,
+}
+
+impl Channels {
+    pub fn take(rcc: &mut rcc::Rcc) -> Option<Self> {
+        if TAKEN.load(Ordering::Relaxed) {
+            None
+        } else {
+            TAKEN.store(true, Ordering::Relaxed);
+
+            // Enable the DMA clocks, DMA1 also clocks the DMAMUX
+            rcc.enable_peripheral_clock(rcc::Peripheral::AHB(rcc::AHBPeripheral::DMA1));
+            #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+            rcc.enable_peripheral_clock(rcc::Peripheral::AHB(rcc::AHBPeripheral::DMA2));
+
+            Some(Self {
+                ch1: Channel { _private: () },
+                ch2: Channel { _private: () },
+                ch3: Channel { _private: () },
+                ch4: Channel { _private: () },
+                ch5: Channel { _private: () },
+                #[cfg(not(any(
+                    feature = "stm32g030",
+                    feature = "stm32g031",
+                    feature = "stm32g041"
+                )))]
+                ch6: Channel { _private: () },
+                #[cfg(not(any(
+                    feature = "stm32g030",
+                    feature = "stm32g031",
+                    feature = "stm32g041"
+                )))]
+                ch7: Channel { _private: () },
+                #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+                ch8: Channel { _private: () },
+                #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+                ch9: Channel { _private: () },
+                #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+                ch10: Channel { _private: () },
+                #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+                ch11: Channel { _private: () },
+                #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+                ch12: Channel { _private: () },
+            })
+        }
+    }
+}
+
+impl<const N: u8> Channel<N> {
+    /// DMA controller of the channel
+    fn dma() -> *const pac::dma1::RegisterBlock {
+        #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+        if N > 7 {
+            // DMA2 has the same layout as DMA1, with fewer channels
+            return pac::DMA2::ptr() as *const pac::dma1::RegisterBlock;
+        }
+
+        pac::DMA1::ptr()
+    }
+
+    /// Channel index in its DMA controller
+    fn index() -> usize {
+        if N > 7 {
+            N as usize - 8
+        } else {
+            N as usize - 1
+        }
+    }
+
+    /// Route a DMAMUX request input to the channel
+    pub fn set_request(&mut self, request: u8) {
+        // The PAC only describes 7 DMAMUX channels with a 6 bits request
+        // ID, so the channel configuration register is accessed directly
+        unsafe {
+            let ccr = (pac::DMAMUX::ptr() as *mut u32).add(N as usize - 1);
+            ccr.write_volatile((ccr.read_volatile() & !0x7f) | request as u32);
+        }
+    }
+
+    /// Set the peripheral register address
+    pub fn set_peripheral_address(&mut self, address: u32) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .par()
+                .write(|w| w.bits(address));
+        }
+    }
+
+    /// Set the memory address
+    pub fn set_memory_address(&mut self, address: u32) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .mar()
+                .write(|w| w.bits(address));
+        }
+    }
+
+    /// Set the number of data items to transfer
+    pub fn set_transfer_count(&mut self, count: u16) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .ndtr()
+                .write(|w| w.bits(count as u32));
+        }
+    }
+
+    /// Get the number of data items left to transfer
+    pub fn get_remaining_count(&mut self) -> u16 {
+        unsafe { (*Self::dma()).ch(Self::index()).ndtr().read().bits() as u16 }
+    }
+
+    /// Set the transfer direction
+    pub fn set_direction(&mut self, direction: Direction) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .modify(|_, w| w.dir().bit(direction == Direction::MemoryToPeripheral));
+        }
+    }
+
+    /// Set the circular mode state
+    pub fn set_circular(&mut self, state: bool) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .modify(|_, w| w.circ().bit(state));
+        }
+    }
+
+    /// Set the memory address increment state
+    pub fn set_memory_increment(&mut self, state: bool) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .modify(|_, w| w.minc().bit(state));
+        }
+    }
+
+    /// Set the peripheral address increment state
+    pub fn set_peripheral_increment(&mut self, state: bool) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .modify(|_, w| w.pinc().bit(state));
+        }
+    }
+
+    /// Set the memory and peripheral data sizes
+    pub fn set_data_size(&mut self, memory: DataSize, peripheral: DataSize) {
+        unsafe {
+            (*Self::dma()).ch(Self::index()).cr().modify(|_, w| {
+                w.msize()
+                    .bits(memory.into())
+                    .psize()
+                    .bits(peripheral.into())
+            });
+        }
+    }
+
+    /// Set the channel priority
+    pub fn set_priority(&mut self, priority: Priority) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .modify(|_, w| w.pl().bits(priority.into()));
+        }
+    }
+
+    /// Set an interrupt state
+    pub fn set_interrupt_state(&mut self, interrupt: Interrupt, state: bool) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .modify(|_, w| match interrupt {
+                    Interrupt::TransferComplete => w.tcie().bit(state),
+                    Interrupt::HalfTransfer => w.htie().bit(state),
+                    Interrupt::TransferError => w.teie().bit(state),
+                });
+        }
+    }
+
+    /// Enable the channel, starting the transfers
+    pub fn enable(&mut self) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .modify(|_, w| w.en().set_bit());
+        }
+    }
+
+    /// Disable the channel
+    pub fn disable(&mut self) {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .modify(|_, w| w.en().clear_bit());
+        }
+    }
+
+    /// Check if the channel is enabled
+    pub fn is_enabled(&mut self) -> bool {
+        unsafe {
+            (*Self::dma())
+                .ch(Self::index())
+                .cr()
+                .read()
+                .en()
+                .bit_is_set()
+        }
+    }
+
+    /// Check if the transfer is complete
+    pub fn is_transfer_complete(&mut self) -> bool {
+        self.flags() & 0b0010 != 0
+    }
+
+    /// Check if half of the transfer is done
+    pub fn is_half_transfer(&mut self) -> bool {
+        self.flags() & 0b0100 != 0
+    }
+
+    /// Check if a transfer error occurred
+    pub fn is_transfer_error(&mut self) -> bool {
+        self.flags() & 0b1000 != 0
+    }
+
+    /// Clear all the channel flags
+    pub fn clear_flags(&mut self) {
+        unsafe {
+            (*Self::dma())
+                .ifcr()
+                .write(|w| w.bits(0b1111 << (Self::index() * 4)));
+        }
+    }
+
+    /// Channel flags: global, transfer complete, half transfer and error
+    fn flags(&mut self) -> u32 {
+        unsafe { ((*Self::dma()).isr().read().bits() >> (Self::index() * 4)) & 0b1111 }
+    }
+}
+
+/// Transfer direction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Read from the peripheral, write to the memory
+    PeripheralToMemory,
+    /// Read from the memory, write to the peripheral
+    MemoryToPeripheral,
+}
+
+/// Data item size
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataSize {
+    /// 8 bits
+    Bits8,
+    /// 16 bits
+    Bits16,
+    /// 32 bits
+    Bits32,
+}
+
+impl From<DataSize> for u8 {
+    fn from(value: DataSize) -> Self {
+        use DataSize::*;
+        match value {
+            Bits8 => 0,
+            Bits16 => 1,
+            Bits32 => 2,
+        }
+    }
+}
+
+/// Channel priority
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Priority {
+    /// Low
+    Low,
+    /// Medium
+    Medium,
+    /// High
+    High,
+    /// Very high
+    VeryHigh,
+}
+
+impl From<Priority> for u8 {
+    fn from(value: Priority) -> Self {
+        use Priority::*;
+        match value {
+            Low => 0,
+            Medium => 1,
+            High => 2,
+            VeryHigh => 3,
+        }
+    }
+}
+
+/// Channel interrupts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// Transfer complete
+    TransferComplete,
+    /// Half transfer
+    HalfTransfer,
+    /// Transfer error
+    TransferError,
+}
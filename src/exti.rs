@@ -0,0 +1,101 @@
+use crate::pac;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+pub struct Exti {
+    rb: *const pac::exti::RegisterBlock,
+}
+
+impl Exti {
+    pub fn take() -> Option<Self> {
+        if TAKEN.load(Ordering::Relaxed) {
+            None
+        } else {
+            TAKEN.store(true, Ordering::Relaxed);
+
+            Some(Self {
+                rb: pac::EXTI::ptr(),
+            })
+        }
+    }
+
+    /// Select the GPIO port connected to an EXTI line (0 to 15)
+    pub(crate) fn select_port(&mut self, line: u8, port: u8) {
+        let shift = (line % 4) * 8;
+        let mask = !(0xffu32 << shift);
+        let value = (port as u32) << shift;
+
+        unsafe {
+            match line / 4 {
+                0 => (*self.rb)
+                    .exticr1()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                1 => (*self.rb)
+                    .exticr2()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                2 => (*self.rb)
+                    .exticr3()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                _ => (*self.rb)
+                    .exticr4()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+            };
+        }
+    }
+
+    /// Set the edges triggering an EXTI line
+    pub(crate) fn set_edge(&mut self, line: u8, edge: Edge) {
+        let (rising, falling) = match edge {
+            Edge::Rising => (true, false),
+            Edge::Falling => (false, true),
+            Edge::Both => (true, true),
+        };
+
+        unsafe {
+            (*self.rb)
+                .rtsr1()
+                .modify(|r, w| w.bits((r.bits() & !(1u32 << line)) | ((rising as u32) << line)));
+            (*self.rb)
+                .ftsr1()
+                .modify(|r, w| w.bits((r.bits() & !(1u32 << line)) | ((falling as u32) << line)));
+        }
+    }
+
+    /// Set the interrupt mask state of an EXTI line
+    pub(crate) fn set_interrupt_state(&mut self, line: u8, state: bool) {
+        unsafe {
+            (*self.rb)
+                .imr1()
+                .modify(|r, w| w.bits((r.bits() & !(1u32 << line)) | ((state as u32) << line)));
+        }
+    }
+}
+
+/// Clear the rising and falling edge pending bits of an EXTI line
+pub(crate) fn clear_pending(line: u8) {
+    // Pending registers are write-1-to-clear, no read-modify-write needed
+    unsafe {
+        (*pac::EXTI::ptr()).rpr1().write(|w| w.bits(1u32 << line));
+        (*pac::EXTI::ptr()).fpr1().write(|w| w.bits(1u32 << line));
+    }
+}
+
+/// Check if an EXTI line has a rising or falling edge pending
+pub(crate) fn is_pending(line: u8) -> bool {
+    unsafe {
+        let exti = &*pac::EXTI::ptr();
+        ((exti.rpr1().read().bits() | exti.fpr1().read().bits()) & (1u32 << line)) != 0
+    }
+}
+
+/// EXTI trigger edge
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// Rising edge
+    Rising,
+    /// Falling edge
+    Falling,
+    /// Rising and falling edges
+    Both,
+}
@@ -1,221 +1,1094 @@
+pub mod af;
+
+use crate::{exti, pac};
 use core::marker::PhantomData;
 
 /// Default mode (reset state)
 type DefaultMode = Analog;
 
+/// Number of alternate functions, AF8 and above only exist on the larger devices
+#[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+const AF_COUNT: u8 = 16;
+#[cfg(not(any(feature = "stm32g0b1", feature = "stm32g0c1")))]
+const AF_COUNT: u8 = 8;
+
 /// Analog mode (type state)
+#[derive(Default)]
 pub struct Analog;
 
 /// Alternate Function mode (type state)
-pub struct AlternateFunction;
+#[derive(Default)]
+pub struct Alternate<const AF: u8, OTYPE = PushPull> {
+    _otype: PhantomData<OTYPE>,
+}
 
 /// Output Push Pull mode (type state)
+#[derive(Default)]
 pub struct PushPull;
 
 /// Floating mode (type state)
+#[derive(Default)]
 pub struct Floating;
 
 /// Pull Up mode (type state)
+#[derive(Default)]
 pub struct PullUp;
 
 /// Pull Down mode (type state)
+#[derive(Default)]
 pub struct PullDown;
 
 /// Opendrain mode (type state)
+#[derive(Default)]
 pub struct OpenDrain;
 
 /// Input mode (type state)
+#[derive(Default)]
 pub struct Input<MODE> {
     _mode: PhantomData<MODE>,
 }
 
 /// Output mode (type state)
+#[derive(Default)]
 pub struct Output<MODE> {
     _mode: PhantomData<MODE>,
 }
 
-pub struct Pin<PORT, MODE = DefaultMode> {
+/// Locked mode (type state), the pin configuration is frozen until reset
+#[derive(Default)]
+pub struct Locked<MODE> {
+    _mode: PhantomData<MODE>,
+}
+
+/// Modes whose configuration can still be changed
+pub trait Configurable {}
+
+impl Configurable for Analog {}
+impl<MODE> Configurable for Input<MODE> {}
+impl<MODE> Configurable for Output<MODE> {}
+impl<const AF: u8, OTYPE> Configurable for Alternate<AF, OTYPE> {}
+impl Configurable for Dynamic {}
+
+/// Dynamic mode (type state), the pin mode is changed and checked at runtime
+pub struct Dynamic {
+    mode: DynamicMode,
+}
+
+/// Dynamic pin mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynamicMode {
+    /// Analog mode
+    Analog,
+    /// Floating input
+    InputFloating,
+    /// Pulled up input
+    InputPullUp,
+    /// Pulled down input
+    InputPullDown,
+    /// Output push-pull
+    OutputPushPull,
+    /// Output opendrain
+    OutputOpenDrain,
+}
+
+impl DynamicMode {
+    /// Check if the pin can be written in this mode
+    pub fn is_output(self) -> bool {
+        matches!(
+            self,
+            DynamicMode::OutputPushPull | DynamicMode::OutputOpenDrain
+        )
+    }
+
+    /// Check if the pin can be read in this mode
+    pub fn is_input(self) -> bool {
+        matches!(
+            self,
+            DynamicMode::InputFloating
+                | DynamicMode::InputPullUp
+                | DynamicMode::InputPullDown
+                | DynamicMode::OutputOpenDrain
+        )
+    }
+}
+
+/// Error of a dynamic pin accessed in the wrong mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynamicError {
+    /// The pin is not in an output mode
+    NotOutput,
+    /// The pin is not in an input mode
+    NotInput,
+}
+
+/// Pin with its port and number known at compile time
+pub struct Pin<PORT, const N: u8, MODE = DefaultMode> {
+    _port: PhantomData<PORT>,
+    mode: MODE,
+}
+
+/// Pin with its port erased, so pins from different ports can share a type
+pub struct ErasedPin<MODE> {
+    port: u8,
     pin: u8,
+    _mode: PhantomData<MODE>,
+}
+
+/// Pins of the same port, accessed together with single register accesses
+///
+/// Bits of the values and masks are the pin numbers of the port.
+pub struct PinGroup<PORT, MODE> {
+    mask: u16,
     _port: PhantomData<PORT>,
     _mode: PhantomData<MODE>,
 }
 
-/// GPIO alternate functions
-pub enum AlternateFunctionList {
-    /// Alternate function 0
-    AF0,
-    /// Alternate function 1
-    AF1,
-    /// Alternate function 2
-    AF2,
-    /// Alternate function 3
-    AF3,
-    /// Alternate function 4
-    AF4,
-    /// Alternate function 5
-    AF5,
-    /// Alternate function 6
-    AF6,
-    /// Alternate function 7
-    AF7,
+impl<PORT, MODE> PinGroup<PORT, MODE> {
+    /// Create an empty pin group
+    pub fn new() -> Self {
+        Self {
+            mask: 0,
+            _port: PhantomData,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Add a pin to the group
+    pub fn with<const N: u8>(mut self, _pin: Pin<PORT, N, MODE>) -> Self {
+        const { assert!(N < 16, "GPIO pin number must be in 0..=15") };
+
+        self.mask |= 1 << N;
+        self
+    }
+
+    /// Remove a pin from the group and give it back, if it is in the group
+    ///
+    /// ```rust
+    /// use stm32g0_ll_drivers::{gpio, rcc};
+    ///
+    /// let mut rcc = rcc::Rcc::take().unwrap();
+    /// let pins = gpio::gpiob::Pins::take(&mut rcc).unwrap();
+    ///
+    /// let mut group = gpio::PinGroup::new()
+    ///     .with(pins.pb0.into_output_push_pull())
+    ///     .with(pins.pb1.into_output_push_pull());
+    ///
+    /// // Reconfigure PB1 on its own
+    /// let pb1 = group.remove::<1>().unwrap().into_floating_input();
+    /// ```
+    pub fn remove<const N: u8>(&mut self) -> Option<Pin<PORT, N, MODE>>
+    where
+        MODE: Default,
+    {
+        const { assert!(N < 16, "GPIO pin number must be in 0..=15") };
+
+        if self.mask & (1 << N) == 0 {
+            return None;
+        }
+        self.mask &= !(1 << N);
+
+        Some(Pin {
+            _port: PhantomData,
+            mode: Default::default(),
+        })
+    }
+
+    /// Mask of the pins in the group
+    pub fn mask(&self) -> u16 {
+        self.mask
+    }
 }
 
-impl From<AlternateFunctionList> for u8 {
-    fn from(value: AlternateFunctionList) -> Self {
-        use AlternateFunctionList::*;
+impl<PORT, MODE> Default for PinGroup<PORT, MODE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// GPIO output speed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Speed {
+    /// Very low speed (reset state)
+    VeryLow,
+    /// Low speed
+    Low,
+    /// High speed
+    High,
+    /// Very high speed
+    VeryHigh,
+}
+
+impl From<Speed> for u8 {
+    fn from(value: Speed) -> Self {
+        use Speed::*;
         match value {
-            AF0 => 0,
-            AF1 => 1,
-            AF2 => 2,
-            AF3 => 3,
-            AF4 => 4,
-            AF5 => 5,
-            AF6 => 6,
-            AF7 => 7,
+            VeryLow => 0,
+            Low => 1,
+            High => 2,
+            VeryHigh => 3,
+        }
+    }
+}
+
+impl<MODE> ErasedPin<MODE> {
+    /// Port index of the pin (0 for GPIOA, 1 for GPIOB, ...)
+    pub fn port_index(&self) -> u8 {
+        self.port
+    }
+
+    /// Pin number within its port
+    pub fn pin_number(&self) -> u8 {
+        self.pin
+    }
+
+    /// Register block of the pin's port
+    fn block(&self) -> &pac::gpioa::RegisterBlock {
+        // All GPIO ports share the same register layout and are 0x400 apart
+        unsafe { &*pac::GPIOA::ptr().byte_add(self.port as usize * 0x400) }
+    }
+
+    fn into_mode<NEW>(self) -> ErasedPin<NEW> {
+        ErasedPin {
+            port: self.port,
+            pin: self.pin,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Configure the pin as analog
+    pub fn into_analog(self) -> ErasedPin<Analog> {
+        self.block()
+            .moder()
+            .modify(|_, w| w.moder(self.pin).analog());
+
+        self.into_mode()
+    }
+
+    /// Configure the pin as output push-pull
+    pub fn into_output_push_pull(self) -> ErasedPin<Output<PushPull>> {
+        self.block()
+            .otyper()
+            .modify(|_, w| w.ot(self.pin).push_pull());
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).floating());
+        self.block()
+            .moder()
+            .modify(|_, w| w.moder(self.pin).output());
+
+        self.into_mode()
+    }
+
+    /// Configure the pin as output opendrain
+    pub fn into_output_open_drain(self) -> ErasedPin<Output<OpenDrain>> {
+        self.block()
+            .otyper()
+            .modify(|_, w| w.ot(self.pin).open_drain());
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).floating());
+        self.block()
+            .moder()
+            .modify(|_, w| w.moder(self.pin).output());
+
+        self.into_mode()
+    }
+
+    /// Configure the pin as input
+    pub fn into_input(self) -> ErasedPin<Input<Floating>> {
+        self.into_floating_input()
+    }
+
+    /// Configure the pin as floating input
+    pub fn into_floating_input(self) -> ErasedPin<Input<Floating>> {
+        self.block()
+            .moder()
+            .modify(|_, w| w.moder(self.pin).input());
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).floating());
+
+        self.into_mode()
+    }
+
+    /// Configure the pin as pulled up input
+    pub fn into_pull_up_input(self) -> ErasedPin<Input<PullUp>> {
+        self.block()
+            .moder()
+            .modify(|_, w| w.moder(self.pin).input());
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).pull_up());
+
+        self.into_mode()
+    }
+
+    /// Configure the pin as pulled down input
+    pub fn into_pull_down_input(self) -> ErasedPin<Input<PullDown>> {
+        self.block()
+            .moder()
+            .modify(|_, w| w.moder(self.pin).input());
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).pull_down());
+
+        self.into_mode()
+    }
+
+    /// Configure the pin as alternate function push-pull
+    pub fn into_alternate<const AF: u8>(self) -> ErasedPin<Alternate<AF, PushPull>> {
+        self.set_alternate::<AF>(false);
+
+        self.into_mode()
+    }
+
+    /// Configure the pin as alternate function opendrain
+    pub fn into_alternate_open_drain<const AF: u8>(self) -> ErasedPin<Alternate<AF, OpenDrain>> {
+        self.set_alternate::<AF>(true);
+
+        self.into_mode()
+    }
+
+    /// Select the alternate function, output type and pull before
+    /// switching MODER, so the pin never drives another function
+    fn set_alternate<const AF: u8>(&self, open_drain: bool) {
+        const { assert!(AF < AF_COUNT, "GPIO alternate function out of range") };
+
+        let gpio = self.block();
+        cortex_m::interrupt::free(|_| unsafe {
+            if self.pin < 8 {
+                gpio.afrl().modify(|_, w| w.afr(self.pin).bits(AF));
+            } else {
+                gpio.afrh().modify(|_, w| w.afr(self.pin - 8).bits(AF));
+            }
+            gpio.otyper().modify(|_, w| w.ot(self.pin).bit(open_drain));
+            gpio.pupdr().modify(|_, w| w.pupdr(self.pin).floating());
+            gpio.moder().modify(|_, w| w.moder(self.pin).alternate());
+        });
+    }
+}
+
+impl<const AF: u8, OTYPE> ErasedPin<Alternate<AF, OTYPE>> {
+    /// Set the alternate function pin speed
+    pub fn set_speed(&mut self, speed: Speed) {
+        self.block()
+            .ospeedr()
+            .modify(|_, w| unsafe { w.ospeedr(self.pin).bits(speed.into()) });
+    }
+
+    /// Configure the alternate function pin as pulled up
+    pub fn pull_up(&mut self) {
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).pull_up());
+    }
+
+    /// Configure the alternate function pin as pulled down
+    pub fn pull_down(&mut self) {
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).pull_down());
+    }
+
+    /// Configure the alternate function pin as floating
+    pub fn floating(&mut self) {
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).floating());
+    }
+}
+
+impl<MODE> ErasedPin<Output<MODE>> {
+    /// Set the output pin speed
+    pub fn set_speed(&mut self, speed: Speed) {
+        self.block()
+            .ospeedr()
+            .modify(|_, w| unsafe { w.ospeedr(self.pin).bits(speed.into()) });
+    }
+
+    /// Set the output pin
+    pub fn set_high(&mut self) {
+        self.block().bsrr().write(|w| w.bs(self.pin).set_bit());
+    }
+
+    /// Clear the output pin
+    pub fn set_low(&mut self) {
+        self.block().bsrr().write(|w| w.br(self.pin).set_bit());
+    }
+
+    /// Check if the output pin is set
+    pub fn is_set_high(&self) -> bool {
+        self.block().odr().read().odr(self.pin).is_high()
+    }
+
+    /// Check if the output pin is cleared
+    pub fn is_set_low(&self) -> bool {
+        !self.is_set_high()
+    }
+
+    /// Toggle the output pin
+    pub fn toggle(&mut self) {
+        if self.is_set_high() {
+            self.set_low();
+        } else {
+            self.set_high();
         }
     }
+
+    /// Configure the output pin as pulled up
+    pub fn pull_up(&mut self) {
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).pull_up());
+    }
+
+    /// Configure the output pin as pulled down
+    pub fn pull_down(&mut self) {
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).pull_down());
+    }
+
+    /// Configure the output pin as floating
+    pub fn floating(&mut self) {
+        self.block()
+            .pupdr()
+            .modify(|_, w| w.pupdr(self.pin).floating());
+    }
+}
+
+impl ErasedPin<Output<OpenDrain>> {
+    /// Check if the open-drain pin is high
+    pub fn is_high(&self) -> bool {
+        self.block().idr().read().idr(self.pin).is_high()
+    }
+
+    /// Check if the open-drain pin is low
+    pub fn is_low(&self) -> bool {
+        !self.is_high()
+    }
+}
+
+impl<MODE> ErasedPin<Input<MODE>> {
+    /// Check if the input pin is high
+    pub fn is_high(&self) -> bool {
+        self.block().idr().read().idr(self.pin).is_high()
+    }
+
+    /// Check if the input pin is low
+    pub fn is_low(&self) -> bool {
+        !self.is_high()
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<MODE> embedded_hal::digital::ErrorType for ErasedPin<MODE> {
+    type Error = core::convert::Infallible;
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<MODE> embedded_hal::digital::OutputPin for ErasedPin<Output<MODE>> {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Self::set_high(self);
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Self::set_low(self);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<MODE> embedded_hal::digital::StatefulOutputPin for ErasedPin<Output<MODE>> {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(Self::is_set_high(self))
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(Self::is_set_low(self))
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        Self::toggle(self);
+        Ok(())
+    }
 }
 
+#[cfg(feature = "embedded-hal")]
+impl embedded_hal::digital::InputPin for ErasedPin<Output<OpenDrain>> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(Self::is_high(self))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(Self::is_low(self))
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<MODE> embedded_hal::digital::InputPin for ErasedPin<Input<MODE>> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(Self::is_high(self))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(Self::is_low(self))
+    }
+}
+
+/// Pins are listed by the smallest package they are bonded out in, the
+/// first list being available on every package. Without a package feature,
+/// all the pins of the chip's largest package are available.
 macro_rules! gpio {
-    ($gpiox:ident, $GPIOX:ident, [$(($pxi:ident, $i:expr),)+]) => {
+    (
+        $gpiox:ident,
+        $GPIOX:ident,
+        $port:expr,
+        [$(($pxi:ident, $i:expr),)*],
+        lqfp48: [$(($pxi48:ident, $i48:expr),)*],
+        lqfp64: [$(($pxi64:ident, $i64:expr),)*],
+        lqfp100: [$(($pxi100:ident, $i100:expr),)*]
+    ) => {
         pub mod $gpiox {
             use crate::pac::$GPIOX;
+            use crate::rcc;
+            use core::sync::atomic::{AtomicBool, Ordering};
             use super::*;
 
-            pub struct Pins<$GPIOX> {
-                $(pub $pxi: Pin<$GPIOX>,)+
+            static TAKEN: AtomicBool = AtomicBool::new(false);
+
+            /// Apply the lock key sequence to the pins of `mask`, returns
+            /// false if the port was already locked
+            fn lock_pins(mask: u16) -> bool {
+                cortex_m::interrupt::free(|_| unsafe {
+                    let lckr = (*$GPIOX::ptr()).lckr();
+                    let lck = (lckr.read().bits() & 0xffff) | mask as u32;
+
+                    // LCKK key write sequence: 1, 0, 1, then read back
+                    lckr.write(|w| w.bits(lck | (1u32 << 16)));
+                    lckr.write(|w| w.bits(lck));
+                    lckr.write(|w| w.bits(lck | (1u32 << 16)));
+                    lckr.read();
+
+                    let r = lckr.read();
+                    r.lckk().bit_is_set() && r.bits() as u16 & mask == mask
+                })
+            }
+
+            pub struct Pins {
+                $(pub $pxi: Pin<$GPIOX, { $i }>,)*
+                $(
+                    #[cfg(not(feature = "lqfp32"))]
+                    pub $pxi48: Pin<$GPIOX, { $i48 }>,
+                )*
+                $(
+                    #[cfg(not(any(
+                        feature = "lqfp32",
+                        feature = "lqfp48",
+                        feature = "stm32g030",
+                        feature = "stm32g031",
+                        feature = "stm32g041"
+                    )))]
+                    pub $pxi64: Pin<$GPIOX, { $i64 }>,
+                )*
+                $(
+                    #[cfg(all(
+                        any(feature = "stm32g0b1", feature = "stm32g0c1"),
+                        not(any(feature = "lqfp32", feature = "lqfp48", feature = "lqfp64"))
+                    ))]
+                    pub $pxi100: Pin<$GPIOX, { $i100 }>,
+                )*
             }
 
-            impl Pins<$GPIOX> {
-                /// Takes all pins from this port
+            impl Pins {
+                /// Takes all pins from this port, enabling the port clock
+                ///
+                /// Returns `None` if the pins were already taken.
                 ///
                 /// # Example
                 ///
                 /// ```rust
                 /// use stm32g0_ll_drivers::{gpio, rcc};
                 ///
-                /// // Take rcc peripheral and enable GPIOA clock
+                /// // Take rcc peripheral
                 /// let mut rcc = rcc::Rcc::take().unwrap();
-                /// rcc.enable_gpio_port_clock(rcc::GPIOPort::GPIOA);
                 ///
                 /// // Take all GPIOA pins
-                /// let pins = gpio::gpioa::Pins::take();
+                /// let pins = gpio::gpioa::Pins::take(&mut rcc).unwrap();
                 ///
                 /// // Take pin 5, convert it into output push-pull and set it set_high
                 /// let mut led = pins.pa5.into_output_push_pull();
                 /// led.set_high();
                 /// ```
-                pub fn take() -> Self {
-                    Self {
-                        $($pxi: Pin {
-                            pin: $i,
-                            _port: PhantomData,
-                            _mode: PhantomData,
-                        },)+
+                pub fn take(rcc: &mut rcc::Rcc) -> Option<Self> {
+                    if TAKEN.load(Ordering::Relaxed) {
+                        None
+                    } else {
+                        TAKEN.store(true, Ordering::Relaxed);
+
+                        // Enable the GPIO port clock
+                        rcc.enable_gpio_port_clock(rcc::GPIOPort::$GPIOX);
+
+                        Some(Self {
+                            $($pxi: Pin {
+                                _port: PhantomData,
+                                mode: Analog,
+                            },)*
+                            $(
+                                #[cfg(not(feature = "lqfp32"))]
+                                $pxi48: Pin {
+                                    _port: PhantomData,
+                                    mode: Analog,
+                                },
+                            )*
+                            $(
+                                #[cfg(not(any(
+                                    feature = "lqfp32",
+                                    feature = "lqfp48",
+                                    feature = "stm32g030",
+                                    feature = "stm32g031",
+                                    feature = "stm32g041"
+                                )))]
+                                $pxi64: Pin {
+                                    _port: PhantomData,
+                                    mode: Analog,
+                                },
+                            )*
+                            $(
+                                #[cfg(all(
+                                    any(feature = "stm32g0b1", feature = "stm32g0c1"),
+                                    not(any(feature = "lqfp32", feature = "lqfp48", feature = "lqfp64"))
+                                ))]
+                                $pxi100: Pin {
+                                    _port: PhantomData,
+                                    mode: Analog,
+                                },
+                            )*
+                        })
                     }
                 }
             }
 
-            impl<MODE> Pin<$GPIOX, MODE> {
+            impl<const N: u8, MODE: Configurable> Pin<$GPIOX, N, MODE> {
+                /// Lock the pin configuration until the next reset
+                ///
+                /// The lock key sequence can only be applied once per port, so
+                /// the pin is given back if its port is already locked. Use
+                /// [`PinGroup::lock`] to lock several pins of a port.
+                ///
+                /// ```rust
+                /// use stm32g0_ll_drivers::{gpio, rcc};
+                ///
+                /// let mut rcc = rcc::Rcc::take().unwrap();
+                /// let pins = gpio::gpioa::Pins::take(&mut rcc).unwrap();
+                ///
+                /// // The motor enable pin can no longer be reconfigured
+                /// let mut enable = pins.pa8.into_output_push_pull().lock().ok().unwrap();
+                /// enable.set_low();
+                /// ```
+                pub fn lock(self) -> Result<Pin<$GPIOX, N, Locked<MODE>>, Self> {
+                    if lock_pins(1 << N) {
+                        Ok(Pin {
+                            _port: PhantomData,
+                            mode: Locked { _mode: PhantomData },
+                        })
+                    } else {
+                        Err(self)
+                    }
+                }
+
+                /// Erase the port from the pin type
+                ///
+                /// ```rust
+                /// use stm32g0_ll_drivers::{gpio, rcc};
+                ///
+                /// let mut rcc = rcc::Rcc::take().unwrap();
+                /// let pa = gpio::gpioa::Pins::take(&mut rcc).unwrap();
+                /// let pb = gpio::gpiob::Pins::take(&mut rcc).unwrap();
+                ///
+                /// // Store pins of different ports in the same array
+                /// let mut leds = [
+                ///     pa.pa5.into_output_push_pull().erase_port(),
+                ///     pb.pb3.into_output_push_pull().erase_port(),
+                /// ];
+                ///
+                /// for led in leds.iter_mut() {
+                ///     led.set_high();
+                /// }
+                /// ```
+                pub fn erase_port(self) -> ErasedPin<MODE> {
+                    ErasedPin {
+                        port: $port,
+                        pin: N,
+                        _mode: PhantomData,
+                    }
+                }
+
+                /// Erase the port from the pin type, same as `erase_port`
+                pub fn downgrade(self) -> ErasedPin<MODE> {
+                    self.erase_port()
+                }
+
+                /// Configure the pin as dynamic, starting as floating input
+                ///
+                /// ```rust
+                /// use stm32g0_ll_drivers::{gpio, rcc};
+                ///
+                /// let mut rcc = rcc::Rcc::take().unwrap();
+                /// let pins = gpio::gpioa::Pins::take(&mut rcc).unwrap();
+                /// let mut pin = pins.pa0.into_dynamic();
+                ///
+                /// // Writing an input pin fails
+                /// assert_eq!(pin.set_high(), Err(gpio::DynamicError::NotOutput));
+                ///
+                /// pin.set_mode(gpio::DynamicMode::OutputPushPull);
+                /// assert_eq!(pin.set_high(), Ok(()));
+                /// ```
+                pub fn into_dynamic(self) -> Pin<$GPIOX, N, Dynamic> {
+                    self.into_floating_input();
+
+                    Pin {
+                        _port: PhantomData,
+                        mode: Dynamic {
+                            mode: DynamicMode::InputFloating,
+                        },
+                    }
+                }
+
                 /// Configure the pin as analog
-                pub fn into_analog(self) -> Pin<$GPIOX, Analog> {
+                pub fn into_analog(self) -> Pin<$GPIOX, N, Analog> {
                     unsafe {
-                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(self.pin).analog());
+                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(N).analog());
                     };
 
                     Pin {
-                        pin: self.pin,
                         _port: PhantomData,
-                        _mode: PhantomData,
+                        mode: Default::default(),
                     }
                 }
 
                 /// Configure the pin as output push-pull
-                pub fn into_output_push_pull(self) -> Pin<$GPIOX, Output<PushPull>> {
+                pub fn into_output_push_pull(self) -> Pin<$GPIOX, N, Output<PushPull>> {
                     unsafe {
-                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(self.pin).output());
                         (*$GPIOX::ptr())
                             .otyper()
-                            .modify(|_, w| w.ot(self.pin).push_pull());
+                            .modify(|_, w| w.ot(N).push_pull());
                         (*$GPIOX::ptr())
                             .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).floating());
+                            .modify(|_, w| w.pupdr(N).floating());
+                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(N).output());
                     };
 
                     Pin {
-                        pin: self.pin,
                         _port: PhantomData,
-                        _mode: PhantomData,
+                        mode: Default::default(),
                     }
                 }
 
                 /// Configure the pin as output opendrain
-                pub fn into_output_open_drain(self) -> Pin<$GPIOX, Output<OpenDrain>> {
+                pub fn into_output_open_drain(self) -> Pin<$GPIOX, N, Output<OpenDrain>> {
                     unsafe {
-                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(self.pin).output());
                         (*$GPIOX::ptr())
                             .otyper()
-                            .modify(|_, w| w.ot(self.pin).open_drain());
+                            .modify(|_, w| w.ot(N).open_drain());
                         (*$GPIOX::ptr())
                             .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).floating());
+                            .modify(|_, w| w.pupdr(N).floating());
+                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(N).output());
                     };
 
                     Pin {
-                        pin: self.pin,
                         _port: PhantomData,
-                        _mode: PhantomData,
+                        mode: Default::default(),
                     }
                 }
 
                 /// Configure the pin as input
-                pub fn into_input(self) -> Pin<$GPIOX, Input<Floating>> {
+                pub fn into_input(self) -> Pin<$GPIOX, N, Input<Floating>> {
+                    self.into_floating_input()
+                }
+
+                /// Configure the pin as floating input
+                pub fn into_floating_input(self) -> Pin<$GPIOX, N, Input<Floating>> {
                     unsafe {
-                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(self.pin).input());
+                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(N).input());
                         (*$GPIOX::ptr())
                             .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).floating());
+                            .modify(|_, w| w.pupdr(N).floating());
                     };
 
                     Pin {
-                        pin: self.pin,
                         _port: PhantomData,
-                        _mode: PhantomData,
+                        mode: Default::default(),
                     }
                 }
 
-                pub fn into_alternate_function(self, function: AlternateFunctionList) -> Pin<$GPIOX, AlternateFunction> {
-                    if self.pin < 8 {
-                        unsafe {
-                            (*$GPIOX::ptr()).afrl().modify(|_, w| w.afr(self.pin).bits(function.into()));
-                        };
+                /// Configure the pin as pulled up input
+                pub fn into_pull_up_input(self) -> Pin<$GPIOX, N, Input<PullUp>> {
+                    unsafe {
+                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(N).input());
+                        (*$GPIOX::ptr())
+                            .pupdr()
+                            .modify(|_, w| w.pupdr(N).pull_up());
+                    };
+
+                    Pin {
+                        _port: PhantomData,
+                        mode: Default::default(),
                     }
-                    else {
-                        unsafe {
-                            (*$GPIOX::ptr()).afrh().modify(|_, w| w.afr(self.pin).bits(function.into()));
-                        };
+                }
+
+                /// Configure the pin as pulled down input
+                pub fn into_pull_down_input(self) -> Pin<$GPIOX, N, Input<PullDown>> {
+                    unsafe {
+                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(N).input());
+                        (*$GPIOX::ptr())
+                            .pupdr()
+                            .modify(|_, w| w.pupdr(N).pull_down());
+                    };
+
+                    Pin {
+                        _port: PhantomData,
+                        mode: Default::default(),
                     }
+                }
+
+                /// Configure the pin as alternate function push-pull
+                pub fn into_alternate<const AF: u8>(self) -> Pin<$GPIOX, N, Alternate<AF, PushPull>> {
+                    self.set_alternate::<AF>(false);
 
                     Pin {
-                        pin: self.pin,
                         _port: PhantomData,
-                        _mode: PhantomData,
+                        mode: Default::default(),
+                    }
+                }
+
+                /// Configure the pin as alternate function opendrain
+                pub fn into_alternate_open_drain<const AF: u8>(self) -> Pin<$GPIOX, N, Alternate<AF, OpenDrain>> {
+                    self.set_alternate::<AF>(true);
+
+                    Pin {
+                        _port: PhantomData,
+                        mode: Default::default(),
+                    }
+                }
+
+                /// Select the alternate function, output type and pull before
+                /// switching MODER, so the pin never drives another function
+                fn set_alternate<const AF: u8>(&self, open_drain: bool) {
+                    const { assert!(AF < AF_COUNT, "GPIO alternate function out of range") };
+
+                    cortex_m::interrupt::free(|_| unsafe {
+                        if N < 8 {
+                            (*$GPIOX::ptr()).afrl().modify(|_, w| w.afr(N).bits(AF));
+                        } else {
+                            (*$GPIOX::ptr()).afrh().modify(|_, w| w.afr(N - 8).bits(AF));
+                        }
+                        (*$GPIOX::ptr())
+                            .otyper()
+                            .modify(|_, w| w.ot(N).bit(open_drain));
+                        (*$GPIOX::ptr())
+                            .pupdr()
+                            .modify(|_, w| w.pupdr(N).floating());
+                        (*$GPIOX::ptr()).moder().modify(|_, w| w.moder(N).alternate());
+                    });
+                }
+
+                /// Temporarily configure the pin as output push-pull
+                pub fn with_push_pull_output<R>(
+                    &mut self,
+                    f: impl FnOnce(&mut Pin<$GPIOX, N, Output<PushPull>>) -> R,
+                ) -> R {
+                    self.with_mode(Pin::<$GPIOX, N, Analog>::into_output_push_pull, f)
+                }
+
+                /// Temporarily configure the pin as output opendrain, starting
+                /// with the line released
+                ///
+                /// ```rust
+                /// use stm32g0_ll_drivers::{gpio, rcc};
+                ///
+                /// let mut rcc = rcc::Rcc::take().unwrap();
+                /// let pins = gpio::gpioa::Pins::take(&mut rcc).unwrap();
+                /// let mut one_wire = pins.pa1.into_pull_up_input();
+                ///
+                /// // Hold the line low for the reset pulse, then release it
+                /// one_wire.with_open_drain_output(|pin| {
+                ///     pin.set_low();
+                ///     for _ in 0..5_000 {}
+                /// });
+                ///
+                /// // Sample the presence pulse
+                /// let present = one_wire.is_low();
+                /// ```
+                pub fn with_open_drain_output<R>(
+                    &mut self,
+                    f: impl FnOnce(&mut Pin<$GPIOX, N, Output<OpenDrain>>) -> R,
+                ) -> R {
+                    self.with_mode(
+                        |pin| {
+                            // Release the line before driving it
+                            unsafe {
+                                (*$GPIOX::ptr())
+                                    .otyper()
+                                    .modify(|_, w| w.ot(N).open_drain());
+                                (*$GPIOX::ptr()).bsrr().write(|w| w.bs(N).set_bit());
+                            }
+
+                            pin.into_output_open_drain()
+                        },
+                        f,
+                    )
+                }
+
+                /// Temporarily configure the pin as floating input
+                pub fn with_floating_input<R>(
+                    &mut self,
+                    f: impl FnOnce(&mut Pin<$GPIOX, N, Input<Floating>>) -> R,
+                ) -> R {
+                    self.with_mode(Pin::<$GPIOX, N, Analog>::into_floating_input, f)
+                }
+
+                /// Temporarily configure the pin as pulled up input
+                pub fn with_pull_up_input<R>(
+                    &mut self,
+                    f: impl FnOnce(&mut Pin<$GPIOX, N, Input<PullUp>>) -> R,
+                ) -> R {
+                    self.with_mode(Pin::<$GPIOX, N, Analog>::into_pull_up_input, f)
+                }
+
+                /// Temporarily configure the pin as pulled down input
+                pub fn with_pull_down_input<R>(
+                    &mut self,
+                    f: impl FnOnce(&mut Pin<$GPIOX, N, Input<PullDown>>) -> R,
+                ) -> R {
+                    self.with_mode(Pin::<$GPIOX, N, Analog>::into_pull_down_input, f)
+                }
+
+                /// Configure the pin with `into` for the duration of `f`, then
+                /// restore its ODR, OTYPER, PUPDR and MODER fields
+                fn with_mode<NEW, R>(
+                    &mut self,
+                    into: fn(Pin<$GPIOX, N, Analog>) -> Pin<$GPIOX, N, NEW>,
+                    f: impl FnOnce(&mut Pin<$GPIOX, N, NEW>) -> R,
+                ) -> R {
+                    let (moder, otyper, pupdr, high) = unsafe {
+                        (
+                            (*$GPIOX::ptr()).moder().read().bits(),
+                            (*$GPIOX::ptr()).otyper().read().bits(),
+                            (*$GPIOX::ptr()).pupdr().read().bits(),
+                            (*$GPIOX::ptr()).odr().read().odr(N).is_high(),
+                        )
+                    };
+
+                    let mut pin = into(Pin {
+                        _port: PhantomData,
+                        mode: Analog,
+                    });
+                    let result = f(&mut pin);
+
+                    let mask2 = 0b11u32 << (N * 2);
+                    let mask1 = 1u32 << N;
+                    cortex_m::interrupt::free(|_| unsafe {
+                        if high {
+                            (*$GPIOX::ptr()).bsrr().write(|w| w.bs(N).set_bit());
+                        } else {
+                            (*$GPIOX::ptr()).bsrr().write(|w| w.br(N).set_bit());
+                        }
+                        (*$GPIOX::ptr())
+                            .pupdr()
+                            .modify(|r, w| w.bits((r.bits() & !mask2) | (pupdr & mask2)));
+                        (*$GPIOX::ptr())
+                            .otyper()
+                            .modify(|r, w| w.bits((r.bits() & !mask1) | (otyper & mask1)));
+                        (*$GPIOX::ptr())
+                            .moder()
+                            .modify(|r, w| w.bits((r.bits() & !mask2) | (moder & mask2)));
+                    });
+
+                    result
+                }
+            }
+
+            impl<const N: u8, const AF: u8, OTYPE> Pin<$GPIOX, N, Alternate<AF, OTYPE>> {
+                /// Set the alternate function pin speed
+                pub fn set_speed(&mut self, speed: Speed) {
+                    unsafe {
+                        (*$GPIOX::ptr())
+                            .ospeedr()
+                            .modify(|_, w| w.ospeedr(N).bits(speed.into()));
+                    }
+                }
+
+                /// Configure the alternate function pin as pulled up
+                pub fn pull_up(&mut self) {
+                    unsafe {
+                        (*$GPIOX::ptr())
+                            .pupdr()
+                            .modify(|_, w| w.pupdr(N).pull_up());
+                    }
+                }
+
+                /// Configure the alternate function pin as pulled down
+                pub fn pull_down(&mut self) {
+                    unsafe {
+                        (*$GPIOX::ptr())
+                            .pupdr()
+                            .modify(|_, w| w.pupdr(N).pull_down());
+                    }
+                }
+
+                /// Configure the alternate function pin as floating
+                pub fn floating(&mut self) {
+                    unsafe {
+                        (*$GPIOX::ptr())
+                            .pupdr()
+                            .modify(|_, w| w.pupdr(N).floating());
                     }
                 }
             }
 
-            impl<MODE> Pin<$GPIOX, Output<MODE>> {
+            impl<const N: u8, MODE> Pin<$GPIOX, N, Output<MODE>> {
+                /// Set the output pin speed
+                pub fn set_speed(&mut self, speed: Speed) {
+                    unsafe {
+                        (*$GPIOX::ptr())
+                            .ospeedr()
+                            .modify(|_, w| w.ospeedr(N).bits(speed.into()));
+                    }
+                }
+
                 /// Set the output pin
                 pub fn set_high(&mut self) {
                     unsafe {
-                        (*$GPIOX::ptr()).bsrr().write(|w| w.bs(self.pin).set_bit());
+                        (*$GPIOX::ptr()).bsrr().write(|w| w.bs(N).set_bit());
                     }
                 }
 
                 /// Clear the output pin
                 pub fn set_low(&mut self) {
                     unsafe {
-                        (*$GPIOX::ptr()).bsrr().write(|w| w.br(self.pin).set_bit());
+                        (*$GPIOX::ptr()).bsrr().write(|w| w.br(N).set_bit());
+                    }
+                }
+
+                /// Check if the output pin is set
+                pub fn is_set_high(&self) -> bool {
+                    unsafe { (*$GPIOX::ptr()).odr().read().odr(N).is_high() }
+                }
+
+                /// Check if the output pin is cleared
+                pub fn is_set_low(&self) -> bool {
+                    !self.is_set_high()
+                }
+
+                /// Toggle the output pin
+                pub fn toggle(&mut self) {
+                    if self.is_set_high() {
+                        self.set_low();
+                    } else {
+                        self.set_high();
                     }
                 }
 
@@ -224,7 +1097,7 @@ macro_rules! gpio {
                     unsafe {
                         (*$GPIOX::ptr())
                             .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).pull_up());
+                            .modify(|_, w| w.pupdr(N).pull_up());
                     }
                 }
 
@@ -233,7 +1106,7 @@ macro_rules! gpio {
                     unsafe {
                         (*$GPIOX::ptr())
                             .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).pull_down());
+                            .modify(|_, w| w.pupdr(N).pull_down());
                     }
                 }
 
@@ -242,37 +1115,422 @@ macro_rules! gpio {
                     unsafe {
                         (*$GPIOX::ptr())
                             .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).floating());
+                            .modify(|_, w| w.pupdr(N).floating());
                     }
                 }
             }
 
-            impl<MODE> Pin<$GPIOX, Input<MODE>> {
-                /// Configure the input pin as pulled up
-                pub fn pull_up(&mut self) {
+            impl<const N: u8> Pin<$GPIOX, N, Output<OpenDrain>> {
+                /// Check if the open-drain pin is high
+                pub fn is_high(&self) -> bool {
+                    unsafe { (*$GPIOX::ptr()).idr().read().idr(N).is_high() }
+                }
+
+                /// Check if the open-drain pin is low
+                pub fn is_low(&self) -> bool {
+                    !self.is_high()
+                }
+            }
+
+            impl<const N: u8, MODE> Pin<$GPIOX, N, Input<MODE>> {
+                /// Check if the input pin is high
+                pub fn is_high(&self) -> bool {
+                    unsafe { (*$GPIOX::ptr()).idr().read().idr(N).is_high() }
+                }
+
+                /// Check if the input pin is low
+                pub fn is_low(&self) -> bool {
+                    !self.is_high()
+                }
+
+                /// Connect the pin to its EXTI line
+                ///
+                /// ```rust
+                /// use stm32g0_ll_drivers::{exti, gpio, rcc};
+                ///
+                /// let mut rcc = rcc::Rcc::take().unwrap();
+                /// let mut exti = exti::Exti::take().unwrap();
+                /// let pins = gpio::gpioc::Pins::take(&mut rcc).unwrap();
+                ///
+                /// // Interrupt on button press
+                /// let mut button = pins.pc13.into_pull_up_input();
+                /// button.make_interrupt_source(&mut exti);
+                /// button.trigger_on_edge(&mut exti, exti::Edge::Falling);
+                /// button.enable_interrupt(&mut exti);
+                ///
+                /// // In the EXTI4_15 interrupt handler
+                /// if button.check_interrupt() {
+                ///     button.clear_interrupt_pending_bit();
+                /// }
+                /// ```
+                pub fn make_interrupt_source(&mut self, exti: &mut exti::Exti) {
+                    exti.select_port(N, $port);
+                }
+
+                /// Set the edges triggering the pin's EXTI line
+                pub fn trigger_on_edge(&mut self, exti: &mut exti::Exti, edge: exti::Edge) {
+                    exti.set_edge(N, edge);
+                }
+
+                /// Enable the pin's EXTI line interrupt
+                pub fn enable_interrupt(&mut self, exti: &mut exti::Exti) {
+                    exti.set_interrupt_state(N, true);
+                }
+
+                /// Disable the pin's EXTI line interrupt
+                pub fn disable_interrupt(&mut self, exti: &mut exti::Exti) {
+                    exti.set_interrupt_state(N, false);
+                }
+
+                /// Clear the pin's EXTI line pending bits
+                pub fn clear_interrupt_pending_bit(&mut self) {
+                    exti::clear_pending(N);
+                }
+
+                /// Check if the pin's EXTI line has an interrupt pending
+                pub fn check_interrupt(&self) -> bool {
+                    exti::is_pending(N)
+                }
+            }
+
+            impl<MODE: Configurable> PinGroup<$GPIOX, MODE> {
+                /// Lock the configuration of the group pins until the next
+                /// reset, with a single lock key sequence
+                ///
+                /// The group is given back if its port is already locked.
+                ///
+                /// ```rust
+                /// use stm32g0_ll_drivers::{gpio, rcc};
+                ///
+                /// let mut rcc = rcc::Rcc::take().unwrap();
+                /// let pins = gpio::gpioa::Pins::take(&mut rcc).unwrap();
+                ///
+                /// // Both motor enable pins can no longer be reconfigured
+                /// let mut group = gpio::PinGroup::new()
+                ///     .with(pins.pa8.into_output_push_pull())
+                ///     .with(pins.pa9.into_output_push_pull())
+                ///     .lock()
+                ///     .ok()
+                ///     .unwrap();
+                /// let mut enable1 = group.remove::<8>().unwrap();
+                /// let mut enable2 = group.remove::<9>().unwrap();
+                /// enable1.set_low();
+                /// enable2.set_low();
+                /// ```
+                pub fn lock(self) -> Result<PinGroup<$GPIOX, Locked<MODE>>, Self> {
+                    if lock_pins(self.mask) {
+                        Ok(PinGroup {
+                            mask: self.mask,
+                            _port: PhantomData,
+                            _mode: PhantomData,
+                        })
+                    } else {
+                        Err(self)
+                    }
+                }
+            }
+
+            impl<MODE> PinGroup<$GPIOX, Output<MODE>> {
+                /// Write the group pins in a single BSRR write
+                ///
+                /// ```rust
+                /// use stm32g0_ll_drivers::{gpio, rcc};
+                ///
+                /// let mut rcc = rcc::Rcc::take().unwrap();
+                /// let pins = gpio::gpiob::Pins::take(&mut rcc).unwrap();
+                ///
+                /// // 4-bit parallel bus on PB0 to PB3
+                /// let mut bus = gpio::PinGroup::new()
+                ///     .with(pins.pb0.into_output_push_pull())
+                ///     .with(pins.pb1.into_output_push_pull())
+                ///     .with(pins.pb2.into_output_push_pull())
+                ///     .with(pins.pb3.into_output_push_pull());
+                ///
+                /// bus.write(0b1010);
+                /// ```
+                pub fn write(&mut self, value: u16) {
+                    let set = value & self.mask;
+                    let reset = !value & self.mask;
+
                     unsafe {
                         (*$GPIOX::ptr())
-                            .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).pull_up());
+                            .bsrr()
+                            .write(|w| w.bits(((reset as u32) << 16) | set as u32));
                     }
                 }
 
-                /// Configure the input pin as pulled down
-                pub fn pull_down(&mut self) {
+                /// Set the group pins selected by `mask`
+                pub fn set(&mut self, mask: u16) {
                     unsafe {
                         (*$GPIOX::ptr())
-                            .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).pull_down());
+                            .bsrr()
+                            .write(|w| w.bits((mask & self.mask) as u32));
                     }
                 }
 
-                /// Configure the input pin as floating
-                pub fn floating(&mut self) {
+                /// Clear the group pins selected by `mask`
+                pub fn reset(&mut self, mask: u16) {
                     unsafe {
                         (*$GPIOX::ptr())
-                            .pupdr()
-                            .modify(|_, w| w.pupdr(self.pin).floating());
+                            .bsrr()
+                            .write(|w| w.bits(((mask & self.mask) as u32) << 16));
+                    }
+                }
+
+                /// Read the output state of the group pins
+                pub fn read_output(&self) -> u16 {
+                    unsafe { (*$GPIOX::ptr()).odr().read().bits() as u16 & self.mask }
+                }
+
+                /// Read the level of the group pins
+                pub fn read(&self) -> u16 {
+                    unsafe { (*$GPIOX::ptr()).idr().read().bits() as u16 & self.mask }
+                }
+            }
+
+            impl<MODE> PinGroup<$GPIOX, Input<MODE>> {
+                /// Read the level of the group pins
+                pub fn read(&self) -> u16 {
+                    unsafe { (*$GPIOX::ptr()).idr().read().bits() as u16 & self.mask }
+                }
+            }
+
+            impl<const N: u8> Pin<$GPIOX, N, Dynamic> {
+                /// Change the dynamic pin mode
+                pub fn set_mode(&mut self, mode: DynamicMode) {
+                    let pin = Pin::<$GPIOX, N, Analog> {
+                        _port: PhantomData,
+                        mode: Analog,
+                    };
+
+                    match mode {
+                        DynamicMode::Analog => {
+                            pin.into_analog();
+                        }
+                        DynamicMode::InputFloating => {
+                            pin.into_floating_input();
+                        }
+                        DynamicMode::InputPullUp => {
+                            pin.into_pull_up_input();
+                        }
+                        DynamicMode::InputPullDown => {
+                            pin.into_pull_down_input();
+                        }
+                        DynamicMode::OutputPushPull => {
+                            pin.into_output_push_pull();
+                        }
+                        DynamicMode::OutputOpenDrain => {
+                            pin.into_output_open_drain();
+                        }
+                    }
+
+                    self.mode.mode = mode;
+                }
+
+                /// Get the dynamic pin mode
+                pub fn mode(&self) -> DynamicMode {
+                    self.mode.mode
+                }
+
+                /// Set the dynamic pin
+                pub fn set_high(&mut self) -> Result<(), DynamicError> {
+                    if !self.mode().is_output() {
+                        return Err(DynamicError::NotOutput);
                     }
+
+                    unsafe {
+                        (*$GPIOX::ptr()).bsrr().write(|w| w.bs(N).set_bit());
+                    }
+
+                    Ok(())
+                }
+
+                /// Clear the dynamic pin
+                pub fn set_low(&mut self) -> Result<(), DynamicError> {
+                    if !self.mode().is_output() {
+                        return Err(DynamicError::NotOutput);
+                    }
+
+                    unsafe {
+                        (*$GPIOX::ptr()).bsrr().write(|w| w.br(N).set_bit());
+                    }
+
+                    Ok(())
+                }
+
+                /// Check if the dynamic pin is set
+                pub fn is_set_high(&self) -> Result<bool, DynamicError> {
+                    if !self.mode().is_output() {
+                        return Err(DynamicError::NotOutput);
+                    }
+
+                    Ok(unsafe { (*$GPIOX::ptr()).odr().read().odr(N).is_high() })
+                }
+
+                /// Toggle the dynamic pin
+                pub fn toggle(&mut self) -> Result<(), DynamicError> {
+                    if self.is_set_high()? {
+                        self.set_low()
+                    } else {
+                        self.set_high()
+                    }
+                }
+
+                /// Check if the dynamic pin is high
+                pub fn is_high(&self) -> Result<bool, DynamicError> {
+                    if !self.mode().is_input() {
+                        return Err(DynamicError::NotInput);
+                    }
+
+                    Ok(unsafe { (*$GPIOX::ptr()).idr().read().idr(N).is_high() })
+                }
+
+                /// Check if the dynamic pin is low
+                pub fn is_low(&self) -> Result<bool, DynamicError> {
+                    self.is_high().map(|high| !high)
+                }
+            }
+
+            impl<const N: u8, MODE> Pin<$GPIOX, N, Locked<Output<MODE>>> {
+                /// Set the locked output pin
+                pub fn set_high(&mut self) {
+                    unsafe {
+                        (*$GPIOX::ptr()).bsrr().write(|w| w.bs(N).set_bit());
+                    }
+                }
+
+                /// Clear the locked output pin
+                pub fn set_low(&mut self) {
+                    unsafe {
+                        (*$GPIOX::ptr()).bsrr().write(|w| w.br(N).set_bit());
+                    }
+                }
+
+                /// Check if the locked output pin is set
+                pub fn is_set_high(&self) -> bool {
+                    unsafe { (*$GPIOX::ptr()).odr().read().odr(N).is_high() }
+                }
+
+                /// Check if the locked output pin is cleared
+                pub fn is_set_low(&self) -> bool {
+                    !self.is_set_high()
+                }
+
+                /// Toggle the locked output pin
+                pub fn toggle(&mut self) {
+                    if self.is_set_high() {
+                        self.set_low();
+                    } else {
+                        self.set_high();
+                    }
+                }
+            }
+
+            impl<const N: u8, MODE> Pin<$GPIOX, N, Locked<Input<MODE>>> {
+                /// Check if the locked input pin is high
+                pub fn is_high(&self) -> bool {
+                    unsafe { (*$GPIOX::ptr()).idr().read().idr(N).is_high() }
+                }
+
+                /// Check if the locked input pin is low
+                pub fn is_low(&self) -> bool {
+                    !self.is_high()
+                }
+            }
+
+            #[cfg(feature = "embedded-hal")]
+            impl<const N: u8, MODE> embedded_hal::digital::ErrorType for Pin<$GPIOX, N, MODE> {
+                type Error = core::convert::Infallible;
+            }
+
+            #[cfg(feature = "embedded-hal")]
+            impl<const N: u8, MODE> embedded_hal::digital::OutputPin for Pin<$GPIOX, N, Output<MODE>> {
+                fn set_high(&mut self) -> Result<(), Self::Error> {
+                    Self::set_high(self);
+                    Ok(())
+                }
+
+                fn set_low(&mut self) -> Result<(), Self::Error> {
+                    Self::set_low(self);
+                    Ok(())
+                }
+            }
+
+            #[cfg(feature = "embedded-hal")]
+            impl<const N: u8, MODE> embedded_hal::digital::StatefulOutputPin for Pin<$GPIOX, N, Output<MODE>> {
+                fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_set_high(self))
+                }
+
+                fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_set_low(self))
+                }
+
+                fn toggle(&mut self) -> Result<(), Self::Error> {
+                    Self::toggle(self);
+                    Ok(())
+                }
+            }
+
+            #[cfg(feature = "embedded-hal")]
+            impl<const N: u8> embedded_hal::digital::InputPin for Pin<$GPIOX, N, Output<OpenDrain>> {
+                fn is_high(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_high(self))
+                }
+
+                fn is_low(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_low(self))
+                }
+            }
+
+            #[cfg(feature = "embedded-hal")]
+            impl<const N: u8, MODE> embedded_hal::digital::InputPin for Pin<$GPIOX, N, Input<MODE>> {
+                fn is_high(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_high(self))
+                }
+
+                fn is_low(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_low(self))
+                }
+            }
+
+            #[cfg(feature = "embedded-hal")]
+            impl<const N: u8, MODE> embedded_hal::digital::OutputPin for Pin<$GPIOX, N, Locked<Output<MODE>>> {
+                fn set_high(&mut self) -> Result<(), Self::Error> {
+                    Self::set_high(self);
+                    Ok(())
+                }
+
+                fn set_low(&mut self) -> Result<(), Self::Error> {
+                    Self::set_low(self);
+                    Ok(())
+                }
+            }
+
+            #[cfg(feature = "embedded-hal")]
+            impl<const N: u8, MODE> embedded_hal::digital::StatefulOutputPin for Pin<$GPIOX, N, Locked<Output<MODE>>> {
+                fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_set_high(self))
+                }
+
+                fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_set_low(self))
+                }
+
+                fn toggle(&mut self) -> Result<(), Self::Error> {
+                    Self::toggle(self);
+                    Ok(())
+                }
+            }
+
+            #[cfg(feature = "embedded-hal")]
+            impl<const N: u8, MODE> embedded_hal::digital::InputPin for Pin<$GPIOX, N, Locked<Input<MODE>>> {
+                fn is_high(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_high(self))
+                }
+
+                fn is_low(&mut self) -> Result<bool, Self::Error> {
+                    Ok(Self::is_low(self))
                 }
             }
         }
@@ -282,6 +1540,7 @@ macro_rules! gpio {
 gpio!(
     gpioa,
     GPIOA,
+    0,
     [
         (pa0, 0),
         (pa1, 1),
@@ -299,12 +1558,16 @@ gpio!(
         (pa13, 13),
         (pa14, 14),
         (pa15, 15),
-    ]
+    ],
+    lqfp48: [],
+    lqfp64: [],
+    lqfp100: []
 );
 
 gpio!(
     gpiob,
     GPIOB,
+    1,
     [
         (pb0, 0),
         (pb1, 1),
@@ -316,52 +1579,69 @@ gpio!(
         (pb7, 7),
         (pb8, 8),
         (pb9, 9),
+    ],
+    lqfp48: [
         (pb10, 10),
         (pb11, 11),
         (pb12, 12),
         (pb13, 13),
         (pb14, 14),
         (pb15, 15),
-    ]
+    ],
+    lqfp64: [],
+    lqfp100: []
 );
 
 gpio!(
     gpioc,
     GPIOC,
+    2,
     [
+        (pc6, 6),
+        (pc14, 14),
+        (pc15, 15),
+    ],
+    lqfp48: [
+        (pc7, 7),
+        (pc13, 13),
+    ],
+    lqfp64: [
         (pc0, 0),
         (pc1, 1),
         (pc2, 2),
         (pc3, 3),
         (pc4, 4),
         (pc5, 5),
-        (pc6, 6),
-        (pc7, 7),
         (pc8, 8),
         (pc9, 9),
         (pc10, 10),
         (pc11, 11),
         (pc12, 12),
-        (pc13, 13),
-        (pc14, 14),
-        (pc15, 15),
-    ]
+    ],
+    lqfp100: []
 );
 
+#[cfg(not(feature = "lqfp32"))]
 gpio!(
     gpiod,
     GPIOD,
-    [
+    3,
+    [],
+    lqfp48: [
         (pd0, 0),
         (pd1, 1),
         (pd2, 2),
         (pd3, 3),
+    ],
+    lqfp64: [
         (pd4, 4),
         (pd5, 5),
         (pd6, 6),
-        (pd7, 7),
         (pd8, 8),
         (pd9, 9),
+    ],
+    lqfp100: [
+        (pd7, 7),
         (pd10, 10),
         (pd11, 11),
         (pd12, 12),
@@ -371,10 +1651,18 @@ gpio!(
     ]
 );
 
+#[cfg(all(
+    any(feature = "stm32g0b1", feature = "stm32g0c1"),
+    not(any(feature = "lqfp32", feature = "lqfp48", feature = "lqfp64"))
+))]
 gpio!(
     gpioe,
     GPIOE,
-    [
+    4,
+    [],
+    lqfp48: [],
+    lqfp64: [],
+    lqfp100: [
         (pe0, 0),
         (pe1, 1),
         (pe2, 2),
@@ -397,10 +1685,16 @@ gpio!(
 gpio!(
     gpiof,
     GPIOF,
+    5,
     [
+        (pf2, 2),
+    ],
+    lqfp48: [
         (pf0, 0),
         (pf1, 1),
-        (pf2, 2),
+    ],
+    lqfp64: [],
+    lqfp100: [
         (pf3, 3),
         (pf4, 4),
         (pf5, 5),
@@ -412,7 +1706,5 @@ gpio!(
         (pf11, 11),
         (pf12, 12),
         (pf13, 13),
-        (pf14, 14),
-        (pf15, 15),
     ]
 );
@@ -0,0 +1,220 @@
+//! Peripheral signal to pin mappings
+//!
+//! Marker traits implemented only for the pin and alternate function
+//! combinations listed in the datasheet alternate function tables, so a
+//! driver taking e.g. a `TxPin<USART2>` rejects wrongly mapped pins at
+//! compile time.
+
+use super::{Alternate, OpenDrain, Pin};
+use crate::pac;
+
+/// USART/LPUART transmit pin
+pub trait TxPin<USART> {}
+
+/// USART/LPUART receive pin
+pub trait RxPin<USART> {}
+
+/// USART/LPUART clear to send pin
+pub trait CtsPin<USART> {}
+
+/// USART/LPUART request to send or driver enable pin
+pub trait RtsPin<USART> {}
+
+/// USART synchronous clock pin
+pub trait CkPin<USART> {}
+
+/// SPI clock pin
+pub trait SckPin<SPI> {}
+
+/// SPI master in slave out pin
+pub trait MisoPin<SPI> {}
+
+/// SPI master out slave in pin
+pub trait MosiPin<SPI> {}
+
+/// SPI slave select pin
+pub trait NssPin<SPI> {}
+
+/// I2C clock pin (opendrain)
+pub trait SclPin<I2C> {}
+
+/// I2C data pin (opendrain)
+pub trait SdaPin<I2C> {}
+
+/// Implement a signal trait for the listed `(port, pin, alternate function)`
+/// combinations, for any output type or opendrain only
+macro_rules! af {
+    ($Trait:ident<$PER:ident>: [$(($GPIOX:ident, $n:literal, $af:literal),)+]) => {
+        $(
+            impl<OTYPE> $Trait<pac::$PER> for Pin<pac::$GPIOX, $n, Alternate<$af, OTYPE>> {}
+        )+
+    };
+    (open_drain $Trait:ident<$PER:ident>: [$(($GPIOX:ident, $n:literal, $af:literal),)+]) => {
+        $(
+            impl $Trait<pac::$PER> for Pin<pac::$GPIOX, $n, Alternate<$af, OpenDrain>> {}
+        )+
+    };
+}
+
+af!(TxPin<USART1>: [(GPIOA, 9, 1), (GPIOB, 6, 0), (GPIOC, 4, 1),]);
+af!(RxPin<USART1>: [(GPIOA, 10, 1), (GPIOB, 7, 0), (GPIOC, 5, 1),]);
+af!(CtsPin<USART1>: [(GPIOA, 11, 1), (GPIOB, 4, 4),]);
+af!(RtsPin<USART1>: [(GPIOA, 12, 1), (GPIOB, 3, 4),]);
+af!(CkPin<USART1>: [(GPIOA, 12, 1), (GPIOB, 3, 4),]);
+
+af!(TxPin<USART2>: [(GPIOA, 2, 1), (GPIOA, 14, 1), (GPIOD, 5, 0),]);
+af!(RxPin<USART2>: [(GPIOA, 3, 1), (GPIOA, 15, 1), (GPIOD, 6, 0),]);
+af!(CtsPin<USART2>: [(GPIOA, 0, 1), (GPIOD, 3, 0),]);
+af!(RtsPin<USART2>: [(GPIOA, 1, 1), (GPIOD, 4, 0),]);
+af!(CkPin<USART2>: [(GPIOA, 1, 1), (GPIOD, 4, 0),]);
+
+#[cfg(any(
+    feature = "stm32g070",
+    feature = "stm32g071",
+    feature = "stm32g081",
+    feature = "stm32g0b1",
+    feature = "stm32g0c1"
+))]
+mod usart3_usart4 {
+    use super::*;
+
+    af!(TxPin<USART3>: [
+        (GPIOB, 2, 4),
+        (GPIOB, 8, 4),
+        (GPIOB, 10, 4),
+        (GPIOC, 4, 0),
+        (GPIOC, 10, 0),
+        (GPIOD, 8, 0),
+    ]);
+    af!(RxPin<USART3>: [
+        (GPIOB, 0, 4),
+        (GPIOB, 9, 4),
+        (GPIOB, 11, 4),
+        (GPIOC, 5, 0),
+        (GPIOC, 11, 0),
+        (GPIOD, 9, 0),
+    ]);
+    af!(CtsPin<USART3>: [(GPIOA, 6, 4), (GPIOB, 13, 4), (GPIOD, 11, 0),]);
+    af!(RtsPin<USART3>: [
+        (GPIOA, 15, 5),
+        (GPIOB, 1, 4),
+        (GPIOB, 14, 4),
+        (GPIOD, 2, 0),
+        (GPIOD, 12, 0),
+    ]);
+    af!(CkPin<USART3>: [
+        (GPIOA, 15, 5),
+        (GPIOB, 1, 4),
+        (GPIOB, 14, 4),
+        (GPIOD, 2, 0),
+        (GPIOD, 12, 0),
+    ]);
+
+    af!(TxPin<USART4>: [(GPIOA, 0, 4), (GPIOC, 10, 1),]);
+    af!(RxPin<USART4>: [(GPIOA, 1, 4), (GPIOC, 11, 1),]);
+    af!(CtsPin<USART4>: [(GPIOB, 7, 4),]);
+    af!(RtsPin<USART4>: [(GPIOA, 15, 4),]);
+    af!(CkPin<USART4>: [(GPIOA, 15, 4),]);
+}
+
+#[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+mod usart5_usart6 {
+    use super::*;
+
+    af!(TxPin<USART5>: [(GPIOB, 0, 8), (GPIOB, 3, 8), (GPIOC, 12, 3), (GPIOE, 10, 3),]);
+    af!(RxPin<USART5>: [(GPIOB, 1, 8), (GPIOB, 4, 8), (GPIOD, 2, 3), (GPIOE, 11, 3),]);
+
+    af!(TxPin<USART6>: [(GPIOA, 4, 3), (GPIOB, 8, 8), (GPIOF, 9, 8),]);
+    af!(RxPin<USART6>: [(GPIOA, 5, 3), (GPIOB, 9, 8), (GPIOF, 10, 8),]);
+}
+
+// The low-power UART is named LPUART on devices with a single one
+#[cfg(any(
+    feature = "stm32g031",
+    feature = "stm32g041",
+    feature = "stm32g071",
+    feature = "stm32g081"
+))]
+mod lpuart {
+    use super::*;
+
+    af!(TxPin<LPUART>: [(GPIOA, 2, 6), (GPIOB, 11, 1), (GPIOC, 1, 1),]);
+    af!(RxPin<LPUART>: [(GPIOA, 3, 6), (GPIOB, 10, 1), (GPIOC, 0, 1),]);
+    af!(CtsPin<LPUART>: [(GPIOA, 6, 6), (GPIOB, 13, 1),]);
+    af!(RtsPin<LPUART>: [(GPIOB, 1, 6), (GPIOB, 12, 1),]);
+}
+
+#[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+mod lpuart1_lpuart2 {
+    use super::*;
+
+    af!(TxPin<LPUART1>: [(GPIOA, 2, 6), (GPIOB, 11, 1), (GPIOC, 1, 1),]);
+    af!(RxPin<LPUART1>: [(GPIOA, 3, 6), (GPIOB, 10, 1), (GPIOC, 0, 1),]);
+    af!(CtsPin<LPUART1>: [(GPIOA, 6, 6), (GPIOB, 13, 1),]);
+    af!(RtsPin<LPUART1>: [(GPIOB, 1, 6), (GPIOB, 12, 1),]);
+
+    af!(TxPin<LPUART2>: [(GPIOC, 6, 3),]);
+    af!(RxPin<LPUART2>: [(GPIOC, 7, 3),]);
+}
+
+af!(SckPin<SPI1>: [(GPIOA, 1, 0), (GPIOA, 5, 0), (GPIOB, 3, 0), (GPIOD, 8, 1),]);
+af!(MisoPin<SPI1>: [(GPIOA, 6, 0), (GPIOA, 11, 0), (GPIOB, 4, 0), (GPIOD, 5, 1),]);
+af!(MosiPin<SPI1>: [
+    (GPIOA, 2, 0),
+    (GPIOA, 7, 0),
+    (GPIOA, 12, 0),
+    (GPIOB, 5, 0),
+    (GPIOD, 6, 1),
+]);
+af!(NssPin<SPI1>: [(GPIOA, 4, 0), (GPIOA, 15, 0), (GPIOB, 0, 0), (GPIOD, 9, 1),]);
+
+af!(SckPin<SPI2>: [
+    (GPIOA, 0, 0),
+    (GPIOB, 8, 1),
+    (GPIOB, 10, 5),
+    (GPIOB, 13, 0),
+    (GPIOD, 1, 1),
+]);
+af!(MisoPin<SPI2>: [
+    (GPIOA, 3, 0),
+    (GPIOA, 9, 4),
+    (GPIOB, 2, 1),
+    (GPIOB, 6, 4),
+    (GPIOB, 14, 0),
+    (GPIOC, 2, 1),
+    (GPIOD, 3, 1),
+]);
+af!(MosiPin<SPI2>: [
+    (GPIOA, 4, 1),
+    (GPIOA, 10, 0),
+    (GPIOB, 7, 1),
+    (GPIOB, 11, 0),
+    (GPIOB, 15, 0),
+    (GPIOC, 3, 1),
+    (GPIOD, 4, 1),
+]);
+af!(NssPin<SPI2>: [(GPIOA, 8, 1), (GPIOB, 9, 5), (GPIOB, 12, 0), (GPIOD, 0, 1),]);
+
+#[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+mod spi3 {
+    use super::*;
+
+    af!(SckPin<SPI3>: [(GPIOB, 3, 9), (GPIOC, 10, 4),]);
+    af!(MisoPin<SPI3>: [(GPIOB, 4, 9), (GPIOC, 11, 4),]);
+    af!(MosiPin<SPI3>: [(GPIOB, 5, 9), (GPIOC, 12, 4),]);
+    af!(NssPin<SPI3>: [(GPIOA, 4, 9), (GPIOA, 15, 9),]);
+}
+
+af!(open_drain SclPin<I2C1>: [(GPIOA, 9, 6), (GPIOB, 6, 6), (GPIOB, 8, 6),]);
+af!(open_drain SdaPin<I2C1>: [(GPIOA, 10, 6), (GPIOB, 7, 6), (GPIOB, 9, 6),]);
+
+af!(open_drain SclPin<I2C2>: [(GPIOA, 11, 6), (GPIOB, 10, 6), (GPIOB, 13, 6),]);
+af!(open_drain SdaPin<I2C2>: [(GPIOA, 12, 6), (GPIOB, 11, 6), (GPIOB, 14, 6),]);
+
+#[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+mod i2c3 {
+    use super::*;
+
+    af!(open_drain SclPin<I2C3>: [(GPIOA, 7, 9), (GPIOB, 3, 6), (GPIOC, 0, 6),]);
+    af!(open_drain SdaPin<I2C3>: [(GPIOA, 6, 9), (GPIOB, 4, 6), (GPIOC, 1, 6),]);
+}
@@ -1,15 +1,59 @@
 #![no_std]
 
-#[cfg(feature = "stm32g0b1")]
-pub use stm32g0::stm32g0b1 as pac;
+#[cfg(feature = "stm32g030")]
+pub use stm32g0::stm32g030 as pac;
+
+#[cfg(feature = "stm32g031")]
+pub use stm32g0::stm32g031 as pac;
+
+#[cfg(feature = "stm32g041")]
+pub use stm32g0::stm32g041 as pac;
+
+#[cfg(feature = "stm32g070")]
+pub use stm32g0::stm32g070 as pac;
 
 #[cfg(feature = "stm32g071")]
 pub use stm32g0::stm32g071 as pac;
 
+#[cfg(feature = "stm32g081")]
+pub use stm32g0::stm32g081 as pac;
+
+#[cfg(feature = "stm32g0b1")]
+pub use stm32g0::stm32g0b1 as pac;
+
+#[cfg(feature = "stm32g0c1")]
+pub use stm32g0::stm32g0c1 as pac;
+
+#[cfg(any(
+    all(
+        feature = "lqfp32",
+        any(feature = "lqfp48", feature = "lqfp64", feature = "lqfp100")
+    ),
+    all(feature = "lqfp48", any(feature = "lqfp64", feature = "lqfp100")),
+    all(feature = "lqfp64", feature = "lqfp100"),
+))]
+compile_error!("Only one package feature can be enabled");
+
+#[cfg(all(
+    feature = "lqfp64",
+    any(feature = "stm32g030", feature = "stm32g031", feature = "stm32g041")
+))]
+compile_error!("The selected chip is not available in LQFP64");
+
+#[cfg(all(
+    feature = "lqfp100",
+    not(any(feature = "stm32g0b1", feature = "stm32g0c1"))
+))]
+compile_error!("The selected chip is not available in LQFP100");
+
 pub trait Taker<T> {
     fn take(self) -> T;
 }
 
-//pub mod adc;
+pub mod adc;
+pub mod dma;
+pub mod exti;
 pub mod gpio;
 pub mod rcc;
+pub mod serial;
+pub mod syscfg;
@@ -4,8 +4,18 @@ use core::sync::atomic::{AtomicBool, Ordering};
 
 static TAKEN: AtomicBool = AtomicBool::new(false);
 
+/// HSI16 internal oscillator frequency
+pub const HSI16_FREQUENCY: u32 = 16_000_000;
+
+/// LSI internal oscillator frequency
+pub const LSI_FREQUENCY: u32 = 32_000;
+
+/// LSE external crystal frequency
+pub const LSE_FREQUENCY: u32 = 32_768;
+
 pub struct Rcc {
     rb: *const pac::rcc::RegisterBlock,
+    hse_frequency: u32,
 }
 
 impl Rcc {
@@ -18,6 +28,7 @@ impl Rcc {
 
                 Some(Self {
                     rb: &*pac::RCC::ptr(),
+                    hse_frequency: 0,
                 })
             }
         }
@@ -56,6 +67,7 @@ impl Rcc {
     /// // Enable the HSI48 clock
     /// rcc.set_hsi48_state(true);
     /// ```
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
     pub fn set_hsi48_state(&mut self, state: bool) {
         unsafe {
             (*self.rb).cr().modify(|_, w| w.hsi48on().bit(state));
@@ -63,12 +75,115 @@ impl Rcc {
     }
 
     /// Check if the HSI48 clock is ready
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
     pub fn is_hsi48_ready(&mut self) -> bool {
         unsafe { (*self.rb).cr().read().hsirdy().is_ready() }
     }
 
+    /// Set the HSE clock frequency, used to compute the clock frequencies
+    pub fn set_hse_frequency(&mut self, frequency: u32) {
+        self.hse_frequency = frequency;
+    }
+
+    /// Get the system clock (SYSCLK) frequency
+    pub fn get_sysclk_frequency(&mut self) -> u32 {
+        unsafe {
+            match (*self.rb).cfgr().read().sws().bits() {
+                0 => HSI16_FREQUENCY >> (*self.rb).cr().read().hsidiv().bits(),
+                1 => self.hse_frequency,
+                2 => self.get_pllr_frequency(),
+                3 => LSI_FREQUENCY,
+                _ => LSE_FREQUENCY,
+            }
+        }
+    }
+
+    /// Get the AHB clock (HCLK) frequency
+    pub fn get_hclk_frequency(&mut self) -> u32 {
+        let shift = match unsafe { (*self.rb).cfgr().read().hpre().bits() } {
+            hpre @ 8..=11 => hpre - 7,
+            hpre @ 12..=15 => hpre - 6,
+            _ => 0,
+        };
+
+        self.get_sysclk_frequency() >> shift
+    }
+
+    /// Get the APB clock (PCLK) frequency
+    pub fn get_pclk_frequency(&mut self) -> u32 {
+        let shift = match unsafe { (*self.rb).cfgr().read().ppre().bits() } {
+            ppre @ 4..=7 => ppre - 3,
+            _ => 0,
+        };
+
+        self.get_hclk_frequency() >> shift
+    }
+
+    /// Get the USART (1 to 6) kernel clock frequency
+    ///
+    /// USARTs without a kernel clock selection are clocked by PCLK.
+    pub fn get_usart_clock_frequency(&mut self, usart: u8) -> u32 {
+        let ccipr = unsafe { (*self.rb).ccipr().read().bits() };
+        let selection = match usart {
+            1 => ccipr & 3,
+            #[cfg(not(any(feature = "stm32g031", feature = "stm32g041")))]
+            2 => (ccipr >> 2) & 3,
+            #[cfg(any(feature = "stm32g070", feature = "stm32g0b1", feature = "stm32g0c1"))]
+            3 => (ccipr >> 4) & 3,
+            _ => 0,
+        };
+
+        match selection {
+            0 => self.get_pclk_frequency(),
+            1 => self.get_sysclk_frequency(),
+            2 => HSI16_FREQUENCY,
+            _ => LSE_FREQUENCY,
+        }
+    }
+
+    /// Get the ADC asynchronous kernel clock frequency, before the ADC
+    /// prescaler
+    pub fn get_adc_clock_frequency(&mut self) -> u32 {
+        match unsafe { (*self.rb).ccipr().read().bits() } >> 30 {
+            0 => self.get_sysclk_frequency(),
+            1 => self.get_pllp_frequency(),
+            _ => HSI16_FREQUENCY,
+        }
+    }
+
+    /// Get the PLL P output (PLLPCLK) frequency
+    fn get_pllp_frequency(&mut self) -> u32 {
+        let pllp = unsafe { (*self.rb).pllcfgr().read().pllp().bits() };
+
+        self.get_pll_vco_frequency() / (pllp as u32 + 1)
+    }
+
+    /// Get the PLL R output (PLLRCLK) frequency
+    fn get_pllr_frequency(&mut self) -> u32 {
+        let pllr = unsafe { (*self.rb).pllcfgr().read().pllr().bits() };
+
+        self.get_pll_vco_frequency() / (pllr as u32 + 1)
+    }
+
+    /// Get the PLL VCO output frequency
+    fn get_pll_vco_frequency(&mut self) -> u32 {
+        let pllcfgr = unsafe { (*self.rb).pllcfgr().read() };
+        let input = match pllcfgr.pllsrc().bits() {
+            2 => HSI16_FREQUENCY,
+            3 => self.hse_frequency,
+            _ => 0,
+        };
+
+        input / (pllcfgr.pllm().bits() as u32 + 1) * pllcfgr.plln().bits() as u32
+    }
+
     pub fn enable_peripheral_clock(&mut self, p: Peripheral) {
         match p {
+            Peripheral::AHB(p) => unsafe {
+                (*self.rb)
+                    .ahbenr()
+                    .modify(|r, w| w.bits(r.bits() | (1u32 << u8::from(p))));
+            },
             Peripheral::APB1(p) => unsafe {
                 (*self.rb)
                     .apbenr1()
@@ -84,6 +199,11 @@ impl Rcc {
 
     pub fn disable_peripheral_clock(&mut self, p: Peripheral) {
         match p {
+            Peripheral::AHB(p) => unsafe {
+                (*self.rb)
+                    .ahbenr()
+                    .modify(|r, w| w.bits(r.bits() & !(1u32 << u8::from(p))));
+            },
             Peripheral::APB1(p) => unsafe {
                 (*self.rb)
                     .apbenr1()
@@ -114,12 +234,43 @@ impl Rcc {
     }
 }
 
-/// RCC's APB1 and APB2 peripherals
+/// RCC's AHB, APB1 and APB2 peripherals
 pub enum Peripheral {
+    AHB(AHBPeripheral),
     APB1(APB1Peripheral),
     APB2(APB2Peripheral),
 }
 
+/// RCC AHB peripherals
+pub enum AHBPeripheral {
+    /// DMA1 and DMAMUX
+    DMA1,
+    /// DMA2
+    DMA2,
+    /// Flash memory interface
+    FLASH,
+    /// CRC
+    CRC,
+    /// AES
+    AES,
+    /// Random Number Generator
+    RNG,
+}
+
+impl From<AHBPeripheral> for u8 {
+    fn from(value: AHBPeripheral) -> Self {
+        use AHBPeripheral::*;
+        match value {
+            DMA1 => 0,
+            DMA2 => 1,
+            FLASH => 8,
+            CRC => 12,
+            AES => 16,
+            RNG => 18,
+        }
+    }
+}
+
 /// RCC APB1 peripherals
 pub enum APB1Peripheral {
     /// Timer 2
@@ -257,6 +408,7 @@ pub enum GPIOPort {
     GPIOB,
     GPIOC,
     GPIOD,
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
     GPIOE,
     GPIOF,
 }
@@ -269,6 +421,7 @@ impl From<GPIOPort> for u8 {
             GPIOB => 1,
             GPIOC => 2,
             GPIOD => 3,
+            #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
             GPIOE => 4,
             GPIOF => 5,
         }
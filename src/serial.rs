@@ -0,0 +1,901 @@
+//! USART serial driver
+//!
+//! ```rust,ignore
+//! use stm32g0_ll_drivers::{gpio, pac, rcc, serial};
+//!
+//! let mut rcc = rcc::Rcc::take().unwrap();
+//! let gpioa = gpio::gpioa::Pins::take(&mut rcc).unwrap();
+//!
+//! let tx = gpioa.pa2.into_alternate::<1>();
+//! let rx = gpioa.pa3.into_alternate::<1>();
+//!
+//! let mut serial =
+//!     serial::Serial::<pac::USART2, _>::new(&mut rcc, (tx, rx), serial::Config::default())
+//!         .unwrap();
+//!
+//! serial.write_all(b"hello\r\n");
+//! ```
+
+pub mod irda;
+pub mod lin;
+pub mod smartcard;
+pub mod spi;
+
+use crate::gpio::af::{RtsPin, RxPin, TxPin};
+use crate::gpio::Pin;
+use crate::{dma, pac, rcc};
+use core::convert::From;
+use core::fmt;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// ISR flags, and matching ICR clear bits for the error flags, used as
+// masks since the field names differ between devices
+const PE: u32 = 1 << 0;
+const FE: u32 = 1 << 1;
+const NE: u32 = 1 << 2;
+const ORE: u32 = 1 << 3;
+const IDLE: u32 = 1 << 4;
+const RXFNE: u32 = 1 << 5;
+const TC: u32 = 1 << 6;
+const TXFNF: u32 = 1 << 7;
+const RTOF: u32 = 1 << 11;
+const RXFT: u32 = 1 << 26;
+const TXFT: u32 = 1 << 27;
+
+/// USART peripherals usable by the serial driver
+pub trait Instance {
+    /// USART number, as used by the RCC kernel clock selection
+    const NUMBER: u8;
+
+    /// DMAMUX receive request input
+    const DMA_RX_REQUEST: u8;
+
+    /// DMAMUX transmit request input
+    const DMA_TX_REQUEST: u8;
+
+    fn ptr() -> *const pac::usart1::RegisterBlock;
+
+    fn peripheral() -> rcc::Peripheral;
+
+    fn taken() -> &'static AtomicBool;
+}
+
+macro_rules! instance {
+    ($USARTX:ident, $number:literal, $bus:ident, $APBXPeripheral:ident, $rx:literal, $tx:literal) => {
+        impl Instance for pac::$USARTX {
+            const NUMBER: u8 = $number;
+            const DMA_RX_REQUEST: u8 = $rx;
+            const DMA_TX_REQUEST: u8 = $tx;
+
+            fn ptr() -> *const pac::usart1::RegisterBlock {
+                pac::$USARTX::ptr()
+            }
+
+            fn peripheral() -> rcc::Peripheral {
+                rcc::Peripheral::$bus(rcc::$APBXPeripheral::$USARTX)
+            }
+
+            fn taken() -> &'static AtomicBool {
+                static TAKEN: AtomicBool = AtomicBool::new(false);
+                &TAKEN
+            }
+        }
+    };
+}
+
+instance!(USART1, 1, APB2, APB2Peripheral, 50, 51);
+instance!(USART2, 2, APB1, APB1Peripheral, 52, 53);
+
+#[cfg(any(
+    feature = "stm32g070",
+    feature = "stm32g071",
+    feature = "stm32g081",
+    feature = "stm32g0b1",
+    feature = "stm32g0c1"
+))]
+instance!(USART3, 3, APB1, APB1Peripheral, 54, 55);
+
+#[cfg(any(
+    feature = "stm32g070",
+    feature = "stm32g071",
+    feature = "stm32g081",
+    feature = "stm32g0b1",
+    feature = "stm32g0c1"
+))]
+instance!(USART4, 4, APB1, APB1Peripheral, 56, 57);
+
+#[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+instance!(USART5, 5, APB1, APB1Peripheral, 74, 75);
+
+#[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+instance!(USART6, 6, APB1, APB1Peripheral, 76, 77);
+
+/// Full-featured USARTs, with FIFOs, smartcard mode and receiver timeout
+pub trait FullFeatured: Instance {}
+
+impl FullFeatured for pac::USART1 {}
+
+#[cfg(not(any(feature = "stm32g030", feature = "stm32g031", feature = "stm32g041")))]
+impl FullFeatured for pac::USART2 {}
+
+#[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+impl FullFeatured for pac::USART3 {}
+
+/// Pins used by a serial port
+pub trait Pins<USART> {}
+
+impl<USART, TX: TxPin<USART>, RX: RxPin<USART>> Pins<USART> for (TX, RX) {}
+
+/// Pins of serial ports whose frame format can be changed with
+/// `Serial::configure`, the smartcard mode ones being excluded
+pub trait ConfigPins<USART> {}
+
+impl<USART, TX: TxPin<USART>, RX: RxPin<USART>> ConfigPins<USART> for (TX, RX) {}
+
+/// RS-485 serial port
+impl<USART, TX: TxPin<USART>, RX: RxPin<USART>, DE: RtsPin<USART>> ConfigPins<USART>
+    for (TX, RX, DE)
+{
+}
+
+/// Half-duplex serial port
+impl<USART, PORT, const N: u8, MODE> ConfigPins<USART> for Pin<PORT, N, MODE> where
+    Self: TxPin<USART>
+{
+}
+
+pub struct Serial<USART, PINS> {
+    rb: *const pac::usart1::RegisterBlock,
+    pins: PINS,
+    _usart: PhantomData<USART>,
+}
+
+impl<USART: Instance, PINS: Pins<USART>> Serial<USART, PINS> {
+    /// Configure and enable a serial port, with the transmitter and receiver enabled
+    pub fn new(rcc: &mut rcc::Rcc, pins: PINS, config: Config) -> Result<Self, ConfigError> {
+        Self::init(rcc, pins, config)
+    }
+}
+
+impl<USART: Instance, PINS: ConfigPins<USART>> Serial<USART, PINS> {
+    /// Apply a new configuration, the serial port is disabled during the update
+    ///
+    /// The configuration is left unchanged on error.
+    pub fn configure(&mut self, rcc: &mut rcc::Rcc, config: Config) -> Result<(), ConfigError> {
+        let clock = rcc.get_usart_clock_frequency(USART::NUMBER);
+        let brr = config.oversampling.brr(clock, config.baudrate)?;
+        self.write_config(brr, config);
+
+        Ok(())
+    }
+}
+
+impl<USART: Instance, TX: TxPin<USART>> Serial<USART, TX> {
+    /// Configure and enable a half-duplex serial port, transmitting and
+    /// receiving on the single TX line
+    ///
+    /// The TX pin is expected in alternate open-drain mode, with a pull-up.
+    pub fn new_half_duplex(
+        rcc: &mut rcc::Rcc,
+        tx: TX,
+        config: Config,
+    ) -> Result<Self, ConfigError> {
+        let mut serial = Self::init(rcc, tx, config)?;
+        serial.while_disabled(|rb| rb.cr3().modify(|_, w| w.hdsel().set_bit()));
+
+        Ok(serial)
+    }
+}
+
+impl<USART: Instance, TX: TxPin<USART>, RX: RxPin<USART>, DE: RtsPin<USART>>
+    Serial<USART, (TX, RX, DE)>
+{
+    /// Configure and enable a serial port for an RS-485 transceiver, with the
+    /// DE pin driven by the hardware around each transmission
+    pub fn new_rs485(
+        rcc: &mut rcc::Rcc,
+        pins: (TX, RX, DE),
+        config: Config,
+        driver_enable: DriverEnable,
+    ) -> Result<Self, ConfigError> {
+        let mut serial = Self::init(rcc, pins, config)?;
+        if let Err(error) = serial.set_driver_enable(driver_enable) {
+            serial.release(rcc);
+            return Err(error);
+        }
+
+        Ok(serial)
+    }
+
+    /// Set the driver enable timings and polarity, the serial port is
+    /// disabled during the update
+    pub fn set_driver_enable(&mut self, driver_enable: DriverEnable) -> Result<(), ConfigError> {
+        if driver_enable.assertion_time > 31 || driver_enable.deassertion_time > 31 {
+            return Err(ConfigError::DriverEnableTime);
+        }
+
+        self.while_disabled(|rb| unsafe {
+            rb.cr1().modify(|_, w| {
+                w.deat()
+                    .bits(driver_enable.assertion_time)
+                    .dedt()
+                    .bits(driver_enable.deassertion_time)
+            });
+            rb.cr3().modify(|_, w| {
+                w.dem()
+                    .set_bit()
+                    .dep()
+                    .bit(driver_enable.polarity == Polarity::ActiveLow)
+            });
+        });
+
+        Ok(())
+    }
+}
+
+impl<USART: Instance, PINS> Serial<USART, PINS> {
+    fn init(rcc: &mut rcc::Rcc, pins: PINS, config: Config) -> Result<Self, ConfigError> {
+        let clock = rcc.get_usart_clock_frequency(USART::NUMBER);
+        let brr = config.oversampling.brr(clock, config.baudrate)?;
+
+        Self::init_with_brr(rcc, pins, brr, config)
+    }
+
+    /// Same as `init`, with the BRR value given instead of the baud rate
+    fn init_with_brr(
+        rcc: &mut rcc::Rcc,
+        pins: PINS,
+        brr: u32,
+        config: Config,
+    ) -> Result<Self, ConfigError> {
+        if USART::taken().load(Ordering::Relaxed) {
+            return Err(ConfigError::Taken);
+        }
+        USART::taken().store(true, Ordering::Relaxed);
+
+        rcc.enable_peripheral_clock(USART::peripheral());
+
+        let mut serial = Self {
+            rb: USART::ptr(),
+            pins,
+            _usart: PhantomData,
+        };
+        serial.write_config(brr, config);
+
+        Ok(serial)
+    }
+
+    /// Disable the serial port and release its pins
+    pub fn release(self, rcc: &mut rcc::Rcc) -> PINS {
+        unsafe {
+            (*self.rb).cr1().reset();
+            (*self.rb).cr2().reset();
+            (*self.rb).cr3().reset();
+        }
+        rcc.disable_peripheral_clock(USART::peripheral());
+        USART::taken().store(false, Ordering::Relaxed);
+
+        self.pins
+    }
+
+    /// Update configuration bits only writable while the serial port is
+    /// disabled, then restore its enable state
+    fn while_disabled<T>(&mut self, f: impl FnOnce(&pac::usart1::RegisterBlock) -> T) -> T {
+        unsafe {
+            let enabled = (*self.rb).cr1().read().ue().bit_is_set();
+            (*self.rb).cr1().modify(|_, w| w.ue().clear_bit());
+            let result = f(&*self.rb);
+            (*self.rb).cr1().modify(|_, w| w.ue().bit(enabled));
+
+            result
+        }
+    }
+
+    /// Write the frame configuration while disabled, then enable the
+    /// transmitter, receiver and serial port
+    fn write_config(&mut self, brr: u32, config: Config) {
+        unsafe {
+            (*self.rb).cr1().modify(|_, w| w.ue().clear_bit());
+
+            (*self.rb).brr().write(|w| w.bits(brr));
+            (*self.rb)
+                .cr2()
+                .modify(|_, w| w.stop().bits(config.stop_bits.into()));
+            (*self.rb).cr1().modify(|_, w| {
+                let (m1, m0) = config.word_length.into();
+                w.m1()
+                    .bit(m1)
+                    .m0()
+                    .bit(m0)
+                    .pce()
+                    .bit(config.parity != Parity::None)
+                    .ps()
+                    .bit(config.parity == Parity::Odd)
+                    .over8()
+                    .bit(config.oversampling == Oversampling::X8)
+            });
+            (*self.rb)
+                .cr1()
+                .modify(|_, w| w.te().set_bit().re().set_bit().ue().set_bit());
+        }
+    }
+
+    /// Write a word (7, 8 or 9 bits), waiting for room in the transmitter
+    pub fn write(&mut self, word: u16) {
+        unsafe {
+            while (*self.rb).isr().read().bits() & TXFNF == 0 {}
+            (*self.rb).tdr().write(|w| w.tdr().bits(word));
+        }
+    }
+
+    /// Write all the bytes of a buffer
+    pub fn write_all(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write(byte as u16);
+        }
+    }
+
+    /// Wait until the transmission of all the written words is complete
+    pub fn flush(&mut self) {
+        unsafe { while (*self.rb).isr().read().bits() & TC == 0 {} }
+    }
+
+    /// Write as many bytes as fit in the transmitter without waiting
+    ///
+    /// Returns the number of bytes written.
+    pub fn write_available(&mut self, bytes: &[u8]) -> usize {
+        let mut count = 0;
+
+        for &byte in bytes {
+            unsafe {
+                if (*self.rb).isr().read().bits() & TXFNF == 0 {
+                    break;
+                }
+                (*self.rb).tdr().write(|w| w.tdr().bits(byte as u16));
+            }
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Read the bytes already received without waiting
+    ///
+    /// Returns the number of bytes read. Reading stops before a reception
+    /// error, which is returned by the next call once the bytes read before
+    /// it are handed over.
+    pub fn read_available(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        let mut count = 0;
+
+        for byte in buffer.iter_mut() {
+            let isr = unsafe { (*self.rb).isr().read().bits() };
+            if isr & (RXFNE | PE | FE | NE | ORE) == 0 {
+                break;
+            }
+            if isr & (PE | FE | NE | ORE) != 0 && count != 0 {
+                break;
+            }
+            *byte = self.read()? as u8;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Read a word (7, 8 or 9 bits), waiting for one to be received
+    ///
+    /// A word received with a parity, framing or noise error is discarded.
+    /// Fails with [`Error::Timeout`] if the receiver timeout is enabled and
+    /// expires first.
+    pub fn read(&mut self) -> Result<u16, Error> {
+        loop {
+            let isr = unsafe { (*self.rb).isr().read().bits() };
+
+            let error = if isr & PE != 0 {
+                Some(Error::Parity)
+            } else if isr & FE != 0 {
+                Some(Error::Framing)
+            } else if isr & NE != 0 {
+                Some(Error::Noise)
+            } else if isr & ORE != 0 {
+                Some(Error::Overrun)
+            } else {
+                None
+            };
+
+            unsafe {
+                if isr & RXFNE == 0 && isr & RTOF != 0 {
+                    (*self.rb).icr().write(|w| w.bits(RTOF));
+                    return Err(Error::Timeout);
+                }
+
+                if let Some(error) = error {
+                    if error != Error::Overrun {
+                        (*self.rb).rdr().read();
+                    }
+                    (*self.rb).icr().write(|w| w.bits(PE | FE | NE | ORE));
+
+                    return Err(error);
+                }
+
+                if isr & RXFNE != 0 {
+                    return Ok((*self.rb).rdr().read().rdr().bits());
+                }
+            }
+        }
+    }
+}
+
+impl<USART: Instance, PINS> Serial<USART, PINS> {
+    /// Use a DMA channel to transmit
+    pub fn with_tx_dma<const C: u8>(&self, mut channel: dma::Channel<C>) -> TxDma<'_, USART, C> {
+        channel.disable();
+        channel.set_request(USART::DMA_TX_REQUEST);
+        channel.set_peripheral_address(unsafe { (*self.rb).tdr().as_ptr() } as u32);
+        channel.set_direction(dma::Direction::MemoryToPeripheral);
+        channel.set_data_size(dma::DataSize::Bits8, dma::DataSize::Bits8);
+        channel.set_memory_increment(true);
+        channel.set_peripheral_increment(false);
+        channel.set_circular(false);
+
+        unsafe {
+            (*self.rb).cr3().modify(|_, w| w.dmat().set_bit());
+        }
+
+        TxDma {
+            channel,
+            _usart: PhantomData,
+        }
+    }
+
+    /// Use a DMA channel to receive continuously into a circular buffer
+    ///
+    /// The IDLE interrupt is enabled, so the receiver can be serviced once a
+    /// frame ends with [`RxDma::handle_idle`].
+    pub fn with_rx_dma<const C: u8>(
+        &self,
+        mut channel: dma::Channel<C>,
+        buffer: &'static mut [u8],
+    ) -> RxDma<'_, USART, C> {
+        assert!(
+            !buffer.is_empty() && buffer.len() <= u16::MAX as usize,
+            "Invalid DMA buffer length"
+        );
+
+        channel.disable();
+        channel.clear_flags();
+        channel.set_request(USART::DMA_RX_REQUEST);
+        channel.set_peripheral_address(unsafe { (*self.rb).rdr().as_ptr() } as u32);
+        channel.set_memory_address(buffer.as_mut_ptr() as u32);
+        channel.set_transfer_count(buffer.len() as u16);
+        channel.set_direction(dma::Direction::PeripheralToMemory);
+        channel.set_data_size(dma::DataSize::Bits8, dma::DataSize::Bits8);
+        channel.set_memory_increment(true);
+        channel.set_peripheral_increment(false);
+        channel.set_circular(true);
+        channel.enable();
+
+        unsafe {
+            (*self.rb).icr().write(|w| w.bits(IDLE));
+            (*self.rb).cr3().modify(|_, w| w.dmar().set_bit());
+            (*self.rb).cr1().modify(|_, w| w.idleie().set_bit());
+        }
+
+        RxDma {
+            channel,
+            buffer,
+            position: 0,
+            _usart: PhantomData,
+        }
+    }
+}
+
+/// Serial transmitter using a DMA channel
+///
+/// The serial port stays borrowed until the DMA channel is released, so it
+/// can't be released meanwhile.
+pub struct TxDma<'a, USART, const C: u8> {
+    channel: dma::Channel<C>,
+    _usart: PhantomData<&'a USART>,
+}
+
+impl<USART: Instance, const C: u8> TxDma<'_, USART, C> {
+    /// Start transmitting a buffer, aborting any transfer in progress
+    pub fn write(&mut self, buffer: &'static [u8]) {
+        assert!(
+            !buffer.is_empty() && buffer.len() <= u16::MAX as usize,
+            "Invalid DMA buffer length"
+        );
+
+        self.channel.disable();
+        self.channel.clear_flags();
+        self.channel.set_memory_address(buffer.as_ptr() as u32);
+        self.channel.set_transfer_count(buffer.len() as u16);
+        self.channel.enable();
+    }
+
+    /// Check if the whole buffer was handed to the transmitter
+    ///
+    /// The last words may still be shifting out, see [`Serial::flush`].
+    pub fn is_complete(&mut self) -> bool {
+        self.channel.is_transfer_complete()
+    }
+
+    /// Stop using the DMA channel and release it
+    pub fn release(mut self) -> dma::Channel<C> {
+        self.channel.disable();
+        unsafe {
+            (*USART::ptr()).cr3().modify(|_, w| w.dmat().clear_bit());
+        }
+
+        self.channel
+    }
+}
+
+/// Serial receiver using a DMA channel and a circular buffer
+///
+/// Words received while the buffer is full overwrite the oldest ones. The
+/// serial port stays borrowed until the DMA channel is released, so it can't
+/// be read from or released meanwhile.
+pub struct RxDma<'a, USART, const C: u8> {
+    channel: dma::Channel<C>,
+    buffer: &'static mut [u8],
+    position: usize,
+    _usart: PhantomData<&'a USART>,
+}
+
+impl<USART: Instance, const C: u8> RxDma<'_, USART, C> {
+    /// Clear the IDLE flag, to be called from the USART interrupt handler
+    ///
+    /// Returns the number of bytes received and not read yet.
+    pub fn handle_idle(&mut self) -> usize {
+        unsafe {
+            (*USART::ptr()).icr().write(|w| w.bits(IDLE));
+        }
+
+        self.available()
+    }
+
+    /// Check if the line went idle after a frame
+    pub fn is_idle(&mut self) -> bool {
+        unsafe { (*USART::ptr()).isr().read().bits() & IDLE != 0 }
+    }
+
+    /// Get the number of bytes received and not read yet
+    pub fn available(&mut self) -> usize {
+        let length = self.buffer.len();
+        let end = (length - self.channel.get_remaining_count() as usize) % length;
+
+        (end + length - self.position) % length
+    }
+
+    /// Read the received bytes, returns the number of bytes read
+    pub fn read(&mut self, bytes: &mut [u8]) -> usize {
+        let length = self.buffer.len();
+        let count = self.available().min(bytes.len());
+        let buffer = self.buffer.as_ptr();
+
+        for byte in bytes[..count].iter_mut() {
+            // The buffer is written by the DMA behind the reference's back
+            *byte = unsafe { buffer.add(self.position).read_volatile() };
+            self.position = (self.position + 1) % length;
+        }
+
+        count
+    }
+
+    /// Stop receiving, and release the DMA channel and buffer
+    pub fn release(mut self) -> (dma::Channel<C>, &'static mut [u8]) {
+        self.channel.disable();
+        unsafe {
+            (*USART::ptr()).cr3().modify(|_, w| w.dmar().clear_bit());
+            (*USART::ptr()).cr1().modify(|_, w| w.idleie().clear_bit());
+        }
+
+        (self.channel, self.buffer)
+    }
+}
+
+impl<USART: FullFeatured, PINS> Serial<USART, PINS> {
+    /// Set the FIFO mode state, the serial port is disabled during the update
+    pub fn set_fifo_state(&mut self, state: bool) {
+        self.while_disabled(|rb| rb.cr1().modify(|_, w| w.fifoen().bit(state)));
+    }
+
+    /// Check if the FIFO mode is enabled
+    pub fn is_fifo_enabled(&mut self) -> bool {
+        unsafe { (*self.rb).cr1().read().fifoen().bit_is_set() }
+    }
+
+    /// Set the transmit FIFO threshold
+    pub fn set_tx_fifo_threshold(&mut self, threshold: FifoThreshold) {
+        unsafe {
+            (*self.rb)
+                .cr3()
+                .modify(|_, w| w.txftcfg().bits(threshold.into()));
+        }
+    }
+
+    /// Set the receive FIFO threshold
+    pub fn set_rx_fifo_threshold(&mut self, threshold: FifoThreshold) {
+        unsafe {
+            (*self.rb)
+                .cr3()
+                .modify(|_, w| w.rxftcfg().bits(threshold.into()));
+        }
+    }
+
+    /// Set a FIFO interrupt state
+    pub fn set_fifo_interrupt_state(&mut self, interrupt: FifoInterrupt, state: bool) {
+        unsafe {
+            match interrupt {
+                FifoInterrupt::TxThreshold => {
+                    (*self.rb).cr3().modify(|_, w| w.txftie().bit(state));
+                }
+                FifoInterrupt::RxThreshold => {
+                    (*self.rb).cr3().modify(|_, w| w.rxftie().bit(state));
+                }
+                FifoInterrupt::TxEmpty => {
+                    (*self.rb).cr1().modify(|_, w| w.txfeie().bit(state));
+                }
+                FifoInterrupt::RxFull => {
+                    (*self.rb).cr1().modify(|_, w| w.rxffie().bit(state));
+                }
+            }
+        }
+    }
+
+    /// Check if the transmit FIFO has reached its threshold
+    pub fn is_tx_fifo_threshold_reached(&mut self) -> bool {
+        unsafe { (*self.rb).isr().read().bits() & TXFT != 0 }
+    }
+
+    /// Check if the receive FIFO has reached its threshold
+    pub fn is_rx_fifo_threshold_reached(&mut self) -> bool {
+        unsafe { (*self.rb).isr().read().bits() & RXFT != 0 }
+    }
+
+    /// Set the receiver timeout, in bit durations after the last received
+    /// word (up to 2^24 - 1), or disable it
+    pub fn set_receiver_timeout(&mut self, timeout: Option<u32>) {
+        unsafe {
+            if let Some(timeout) = timeout {
+                assert!(timeout < 1 << 24, "Receiver timeout out of range");
+                (*self.rb).rtor().modify(|_, w| w.rto().bits(timeout));
+                (*self.rb).icr().write(|w| w.bits(RTOF));
+            }
+            (*self.rb)
+                .cr2()
+                .modify(|_, w| w.rtoen().bit(timeout.is_some()));
+        }
+    }
+}
+
+impl<USART: Instance, PINS> fmt::Write for Serial<USART, PINS> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_all(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Serial port configuration
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub baudrate: u32,
+    pub word_length: WordLength,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+    pub oversampling: Oversampling,
+}
+
+impl Default for Config {
+    /// 115200 bauds, 8 data bits, no parity, 1 stop bit
+    fn default() -> Self {
+        Self {
+            baudrate: 115_200,
+            word_length: WordLength::Bits8,
+            parity: Parity::None,
+            stop_bits: StopBits::Stop1,
+            oversampling: Oversampling::X16,
+        }
+    }
+}
+
+/// Word length, including the parity bit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordLength {
+    /// 7 bits
+    Bits7,
+    /// 8 bits
+    Bits8,
+    /// 9 bits
+    Bits9,
+}
+
+impl From<WordLength> for (bool, bool) {
+    /// Convert to the (M1, M0) bits
+    fn from(value: WordLength) -> Self {
+        use WordLength::*;
+        match value {
+            Bits7 => (true, false),
+            Bits8 => (false, false),
+            Bits9 => (false, true),
+        }
+    }
+}
+
+/// Parity control
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parity {
+    /// No parity bit
+    None,
+    /// Even parity
+    Even,
+    /// Odd parity
+    Odd,
+}
+
+/// Number of stop bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopBits {
+    /// 1 stop bit
+    Stop1,
+    /// 0.5 stop bit
+    Stop0p5,
+    /// 2 stop bits
+    Stop2,
+    /// 1.5 stop bits
+    Stop1p5,
+}
+
+impl From<StopBits> for u8 {
+    fn from(value: StopBits) -> Self {
+        use StopBits::*;
+        match value {
+            Stop1 => 0,
+            Stop0p5 => 1,
+            Stop2 => 2,
+            Stop1p5 => 3,
+        }
+    }
+}
+
+/// Receiver oversampling
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Oversampling {
+    /// Oversampling by 16, better noise tolerance
+    X16,
+    /// Oversampling by 8, higher maximum baud rate
+    X8,
+}
+
+impl Oversampling {
+    /// Compute the BRR register value
+    fn brr(self, clock: u32, baudrate: u32) -> Result<u32, ConfigError> {
+        if baudrate == 0 {
+            return Err(ConfigError::Baudrate);
+        }
+
+        let usartdiv = match self {
+            Oversampling::X16 => (clock + baudrate / 2) / baudrate,
+            Oversampling::X8 => (2 * clock + baudrate / 2) / baudrate,
+        };
+        if !(16..=0xffff).contains(&usartdiv) {
+            return Err(ConfigError::Baudrate);
+        }
+
+        Ok(match self {
+            Oversampling::X16 => usartdiv,
+            Oversampling::X8 => (usartdiv & !0xf) | ((usartdiv & 0xf) >> 1),
+        })
+    }
+}
+
+/// RS-485 driver enable configuration
+///
+/// Times are in sample time units, 1/16 or 1/8 of a bit time depending on
+/// the oversampling.
+#[derive(Clone, Copy, Debug)]
+pub struct DriverEnable {
+    pub polarity: Polarity,
+    /// Time between the driver enable activation and the start bit (0 to 31)
+    pub assertion_time: u8,
+    /// Time between the end of the last stop bit and the driver enable
+    /// deactivation (0 to 31)
+    pub deassertion_time: u8,
+}
+
+impl Default for DriverEnable {
+    /// Active high, no assertion or deassertion time
+    fn default() -> Self {
+        Self {
+            polarity: Polarity::ActiveHigh,
+            assertion_time: 0,
+            deassertion_time: 0,
+        }
+    }
+}
+
+/// Signal polarity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    /// Active high
+    ActiveHigh,
+    /// Active low
+    ActiveLow,
+}
+
+/// FIFO threshold, as a fraction of the 8 words depth
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FifoThreshold {
+    /// 1/8 (1 word)
+    Eighth,
+    /// 1/4 (2 words)
+    Quarter,
+    /// 1/2 (4 words)
+    Half,
+    /// 3/4 (6 words)
+    ThreeQuarters,
+    /// 7/8 (7 words)
+    SevenEighths,
+    /// Full or empty (8 words)
+    Full,
+}
+
+impl From<FifoThreshold> for u8 {
+    fn from(value: FifoThreshold) -> Self {
+        use FifoThreshold::*;
+        match value {
+            Eighth => 0,
+            Quarter => 1,
+            Half => 2,
+            ThreeQuarters => 3,
+            SevenEighths => 4,
+            Full => 5,
+        }
+    }
+}
+
+/// FIFO interrupts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FifoInterrupt {
+    /// Transmit FIFO emptied down to its threshold
+    TxThreshold,
+    /// Receive FIFO filled up to its threshold
+    RxThreshold,
+    /// Transmit FIFO empty
+    TxEmpty,
+    /// Receive FIFO full
+    RxFull,
+}
+
+/// Serial port initialization and configuration errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The USART is already in use
+    Taken,
+    /// The baud rate is zero or can't be reached from the USART kernel clock
+    Baudrate,
+    /// The driver enable assertion or deassertion time is above 31
+    DriverEnableTime,
+    /// The smartcard clock prescaler is not in 1 to 31
+    Prescaler,
+    /// The smartcard retries count is above 7
+    Retries,
+}
+
+/// Serial reception errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Parity error
+    Parity,
+    /// Framing error (missing stop bit or break)
+    Framing,
+    /// Noise detected on the line
+    Noise,
+    /// Receiver overrun, words were lost
+    Overrun,
+    /// Receiver timeout expired
+    Timeout,
+}
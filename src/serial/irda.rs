@@ -0,0 +1,86 @@
+//! USART IrDA SIR encoder and decoder
+//!
+//! The serial port keeps its usual read and write API. Enabling the IrDA mode
+//! selects 1 stop bit and disables the synchronous, smartcard, LIN and
+//! half-duplex modes.
+
+use super::{Instance, Serial, StopBits};
+use crate::pac;
+
+/// USARTs with an IrDA SIR encoder and decoder
+pub trait IrdaInstance: Instance {}
+
+impl IrdaInstance for pac::USART1 {}
+impl IrdaInstance for pac::USART2 {}
+
+#[cfg(any(
+    feature = "stm32g070",
+    feature = "stm32g071",
+    feature = "stm32g081",
+    feature = "stm32g0b1",
+    feature = "stm32g0c1"
+))]
+impl IrdaInstance for pac::USART3 {}
+
+#[cfg(any(
+    feature = "stm32g070",
+    feature = "stm32g071",
+    feature = "stm32g081",
+    feature = "stm32g0b1",
+    feature = "stm32g0c1"
+))]
+impl IrdaInstance for pac::USART4 {}
+
+impl<USART: IrdaInstance, PINS> Serial<USART, PINS> {
+    /// Enable the IrDA SIR mode, the serial port is disabled during the update
+    pub fn enable_irda(&mut self, mode: IrdaMode) {
+        let (low_power, prescaler) = match mode {
+            IrdaMode::Normal => (false, 1),
+            IrdaMode::LowPower { prescaler } => {
+                assert!(prescaler != 0, "IrDA prescaler out of range");
+                (true, prescaler)
+            }
+        };
+
+        self.while_disabled(|rb| unsafe {
+            rb.gtpr().modify(|_, w| w.psc().bits(prescaler));
+            rb.cr2().modify(|_, w| {
+                w.stop()
+                    .bits(StopBits::Stop1.into())
+                    .clken()
+                    .clear_bit()
+                    .linen()
+                    .clear_bit()
+            });
+            rb.cr3().modify(|_, w| {
+                w.scen()
+                    .clear_bit()
+                    .hdsel()
+                    .clear_bit()
+                    .irlp()
+                    .bit(low_power)
+                    .iren()
+                    .set_bit()
+            });
+        });
+    }
+
+    /// Disable the IrDA SIR mode, the serial port is disabled during the update
+    pub fn disable_irda(&mut self) {
+        self.while_disabled(|rb| {
+            rb.cr3()
+                .modify(|_, w| w.irlp().clear_bit().iren().clear_bit());
+        });
+    }
+}
+
+/// IrDA SIR power mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrdaMode {
+    /// Pulses of 3/16 bit duration
+    Normal,
+    /// Pulses of 3 low-power clock periods, the low-power clock being the
+    /// USART kernel clock divided by the prescaler (1 to 255), close to
+    /// 1.8432 MHz
+    LowPower { prescaler: u8 },
+}
@@ -0,0 +1,171 @@
+//! USART LIN master and slave mode
+//!
+//! The serial port must be configured with 8 data bits and no parity.
+//! Enabling the LIN mode selects 1 stop bit and disables the synchronous,
+//! smartcard, IrDA and half-duplex modes.
+
+use super::{Error, FullFeatured, Serial, StopBits};
+
+// LIN break detection flag and clear bit
+const LBDF: u32 = 1 << 8;
+
+/// Synchronization byte following the break
+pub const SYNC: u8 = 0x55;
+
+impl<USART: FullFeatured, PINS> Serial<USART, PINS> {
+    /// Enable the LIN mode, the serial port is disabled during the update
+    pub fn enable_lin(&mut self, break_length: BreakLength) {
+        self.while_disabled(|rb| unsafe {
+            rb.cr2().modify(|_, w| {
+                w.stop()
+                    .bits(StopBits::Stop1.into())
+                    .clken()
+                    .clear_bit()
+                    .lbdl()
+                    .bit(break_length == BreakLength::Bits11)
+                    .linen()
+                    .set_bit()
+            });
+            rb.cr3()
+                .modify(|_, w| w.scen().clear_bit().hdsel().clear_bit().iren().clear_bit());
+        });
+    }
+
+    /// Disable the LIN mode, the serial port is disabled during the update
+    pub fn disable_lin(&mut self) {
+        self.while_disabled(|rb| {
+            rb.cr2()
+                .modify(|_, w| w.linen().clear_bit().lbdie().clear_bit());
+        });
+    }
+
+    /// Set the LIN break detection interrupt state
+    pub fn set_lin_break_interrupt_state(&mut self, state: bool) {
+        unsafe {
+            (*self.rb).cr2().modify(|_, w| w.lbdie().bit(state));
+        }
+    }
+
+    /// Check if a LIN break was detected
+    pub fn is_lin_break_detected(&mut self) -> bool {
+        unsafe { (*self.rb).isr().read().bits() & LBDF != 0 }
+    }
+
+    /// Clear the LIN break detection flag
+    pub fn clear_lin_break(&mut self) {
+        unsafe {
+            (*self.rb).icr().write(|w| w.bits(LBDF));
+        }
+    }
+
+    /// Send a break, after the word being transmitted
+    pub fn send_break(&mut self) {
+        unsafe {
+            (*self.rb).rqr().write(|w| w.sbkrq().set_bit());
+        }
+    }
+
+    /// Send a LIN header: break, synchronization byte and protected
+    /// identifier of a frame identifier (0 to 63)
+    pub fn send_lin_header(&mut self, id: u8) {
+        self.send_break();
+        self.write(SYNC as u16);
+        self.write(protected_id(id) as u16);
+    }
+
+    /// Wait for a LIN header and return its frame identifier
+    ///
+    /// The break itself, received as a word with a framing error, is
+    /// skipped. A wrong synchronization byte is reported as a framing error
+    /// and a wrong protected identifier as a parity error.
+    pub fn read_lin_header(&mut self) -> Result<u8, Error> {
+        while !self.is_lin_break_detected() {}
+        self.clear_lin_break();
+
+        let sync = loop {
+            match self.read() {
+                Err(Error::Framing) => continue,
+                result => break result?,
+            }
+        };
+        if sync != SYNC as u16 {
+            return Err(Error::Framing);
+        }
+
+        let pid = self.read()? as u8;
+        let id = pid & 0x3f;
+        if protected_id(id) != pid {
+            return Err(Error::Parity);
+        }
+
+        Ok(id)
+    }
+}
+
+/// Compute the protected identifier of a frame identifier (0 to 63)
+///
+/// ```rust
+/// use stm32g0_ll_drivers::serial::lin;
+///
+/// assert_eq!(lin::protected_id(0x3c), 0x3c);
+/// assert_eq!(lin::protected_id(0x01), 0xc1);
+/// ```
+pub fn protected_id(id: u8) -> u8 {
+    let bit = |n: u8| (id >> n) & 1;
+    let p0 = bit(0) ^ bit(1) ^ bit(2) ^ bit(4);
+    let p1 = !(bit(1) ^ bit(3) ^ bit(4) ^ bit(5)) & 1;
+
+    (id & 0x3f) | (p0 << 6) | (p1 << 7)
+}
+
+/// Compute the checksum of a frame's data
+///
+/// Diagnostic frames (identifiers 60 to 63) always use the classic checksum,
+/// whatever the requested model.
+///
+/// ```rust
+/// use stm32g0_ll_drivers::serial::lin;
+///
+/// let data = [0x4a, 0x55, 0x93, 0xe5];
+///
+/// assert_eq!(lin::checksum(lin::Checksum::Classic, 0x50, &data), 0xe6);
+/// assert_eq!(lin::checksum(lin::Checksum::Enhanced, 0x50, &data), 0x96);
+/// assert_eq!(lin::checksum(lin::Checksum::Enhanced, 0x3c, &data), 0xe6);
+/// ```
+pub fn checksum(model: Checksum, pid: u8, data: &[u8]) -> u8 {
+    let diagnostic = (pid & 0x3f) >= 60;
+    let initial = match model {
+        Checksum::Enhanced if !diagnostic => pid as u16,
+        _ => 0,
+    };
+
+    // Sum with carry wrapped around
+    let sum = data.iter().fold(initial, |sum, &byte| {
+        let sum = sum + byte as u16;
+        if sum > 0xff {
+            sum - 0xff
+        } else {
+            sum
+        }
+    });
+
+    !(sum as u8)
+}
+
+/// LIN break detection length
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BreakLength {
+    /// 10 bits
+    Bits10,
+    /// 11 bits
+    Bits11,
+}
+
+/// LIN checksum model
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Checksum {
+    /// Data bytes only (LIN 1.x)
+    Classic,
+    /// Protected identifier and data bytes (LIN 2.x)
+    Enhanced,
+}
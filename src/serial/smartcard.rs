@@ -0,0 +1,197 @@
+//! USART smartcard (ISO 7816-3) mode
+//!
+//! The TX pin is the card I/O line, in alternate open-drain mode with a
+//! pull-up, and the CK pin provides the card clock.
+
+use super::{
+    Config as SerialConfig, ConfigError, Error, FullFeatured, Oversampling, Parity, Serial,
+    StopBits, WordLength,
+};
+use crate::gpio::af::{CkPin, TxPin};
+use crate::rcc;
+
+// End of block flag and clear bit
+const EOBF: u32 = 1 << 12;
+
+/// Maximum answer to reset length
+pub const ATR_MAX_LENGTH: usize = 33;
+
+/// Initial waiting time, in elementary time units
+const INITIAL_WAITING_TIME: u32 = 9600;
+
+impl<USART: FullFeatured, TX: TxPin<USART>, CK: CkPin<USART>> Serial<USART, (TX, CK)> {
+    /// Configure and enable a serial port in smartcard mode
+    ///
+    /// Frames are 8 data bits with even parity and 1.5 stop bits. Fails with
+    /// [`ConfigError::Baudrate`] if `2 * prescaler * etu` doesn't fit in the
+    /// baud rate register (16 to 65535). The frame format can't be changed
+    /// afterwards with `Serial::configure`.
+    pub fn new_smartcard(
+        rcc: &mut rcc::Rcc,
+        pins: (TX, CK),
+        config: Config,
+    ) -> Result<Self, ConfigError> {
+        if !(1..32).contains(&config.prescaler) {
+            return Err(ConfigError::Prescaler);
+        }
+        if config.retries > 7 {
+            return Err(ConfigError::Retries);
+        }
+
+        // One elementary time unit lasts `etu` card clock cycles, each being
+        // `2 * prescaler` kernel clock cycles
+        let brr = 2 * config.prescaler as u32 * config.etu as u32;
+        if !(16..=0xffff).contains(&brr) {
+            return Err(ConfigError::Baudrate);
+        }
+
+        let mut serial = Self::init_with_brr(
+            rcc,
+            pins,
+            brr,
+            SerialConfig {
+                word_length: WordLength::Bits9,
+                parity: Parity::Even,
+                stop_bits: StopBits::Stop1p5,
+                oversampling: Oversampling::X16,
+                // Unused, the BRR value is given
+                ..SerialConfig::default()
+            },
+        )?;
+
+        serial.while_disabled(|rb| unsafe {
+            rb.gtpr()
+                .write(|w| w.psc().bits(config.prescaler).gt().bits(config.guard_time));
+            rb.cr2().modify(|_, w| w.clken().set_bit());
+            rb.cr3().modify(|_, w| {
+                w.scen()
+                    .set_bit()
+                    .nack()
+                    .bit(config.nack)
+                    .scarcnt()
+                    .bits(config.retries)
+            });
+        });
+
+        Ok(serial)
+    }
+
+    /// Set the block length for T=1 transfers, counting the prologue and
+    /// epilogue bytes after the length byte
+    pub fn set_block_length(&mut self, length: u8) {
+        unsafe {
+            (*self.rb).rtor().modify(|_, w| w.blen().bits(length));
+            (*self.rb).icr().write(|w| w.bits(EOBF));
+        }
+    }
+
+    /// Check if the end of the block was reached
+    pub fn is_end_of_block(&mut self) -> bool {
+        unsafe { (*self.rb).isr().read().bits() & EOBF != 0 }
+    }
+
+    /// Clear the end of block flag
+    pub fn clear_end_of_block(&mut self) {
+        unsafe {
+            (*self.rb).icr().write(|w| w.bits(EOBF));
+        }
+    }
+
+    /// Receive the answer to reset, once the card reset is released
+    ///
+    /// The ATR length is found from its T0 and TDi bytes. The first byte is
+    /// waited for indefinitely, then each byte must come within the initial
+    /// waiting time. The receiver timeout is disabled on return.
+    pub fn read_atr(&mut self) -> Result<Atr, Error> {
+        let mut atr = Atr {
+            bytes: [0; ATR_MAX_LENGTH],
+            length: 0,
+        };
+
+        self.set_receiver_timeout(Some(INITIAL_WAITING_TIME));
+
+        // TS and T0, then the interface, historical and check bytes
+        let mut expected = 2;
+        let mut y_position = 1;
+        let mut check = false;
+
+        while atr.length < expected.min(ATR_MAX_LENGTH) {
+            let byte = match self.read() {
+                Ok(word) => word as u8,
+                Err(error) => {
+                    self.set_receiver_timeout(None);
+                    return Err(error);
+                }
+            };
+            let position = atr.length;
+            atr.bytes[position] = byte;
+            atr.length += 1;
+
+            if position == y_position {
+                let y = byte >> 4;
+
+                if position == 1 {
+                    // T0 holds the number of historical bytes
+                    expected += (byte & 0xf) as usize;
+                } else if byte & 0xf != 0 && !check {
+                    // A protocol other than T=0 adds a check byte
+                    check = true;
+                    expected += 1;
+                }
+
+                expected += y.count_ones() as usize;
+                if y & 0b1000 != 0 {
+                    y_position = position + y.count_ones() as usize;
+                }
+            }
+        }
+
+        self.set_receiver_timeout(None);
+
+        Ok(atr)
+    }
+}
+
+/// Smartcard mode configuration
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// Card clock prescaler (1 to 31), the card clock is the USART kernel
+    /// clock divided by twice this value
+    pub prescaler: u8,
+    /// Card clock cycles per elementary time unit (F / D)
+    pub etu: u16,
+    /// Guard time, in elementary time units
+    pub guard_time: u8,
+    /// Send a NACK on parity errors
+    pub nack: bool,
+    /// Automatic retransmissions and receptions retries (0 to 7)
+    pub retries: u8,
+}
+
+impl Default for Config {
+    /// 4 MHz card clock from a 16 MHz kernel clock, default F / D (372),
+    /// NACK and 3 retries as for T=0
+    fn default() -> Self {
+        Self {
+            prescaler: 2,
+            etu: 372,
+            guard_time: 16,
+            nack: true,
+            retries: 3,
+        }
+    }
+}
+
+/// Answer to reset
+#[derive(Clone, Copy, Debug)]
+pub struct Atr {
+    bytes: [u8; ATR_MAX_LENGTH],
+    length: usize,
+}
+
+impl Atr {
+    /// Get the received bytes, starting with TS
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+}
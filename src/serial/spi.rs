@@ -0,0 +1,242 @@
+//! USART synchronous mode, as an SPI master or slave
+//!
+//! The TX pin is MOSI and the RX pin MISO in master mode, and the other way
+//! around in slave mode. Words are 8 bits.
+
+use super::{
+    ConfigError, Error, FullFeatured, Instance, Oversampling, FE, NE, ORE, PE, RXFNE, TC, TXFNF,
+};
+use crate::gpio::af::{CkPin, RxPin, TxPin};
+use crate::{pac, rcc};
+use core::marker::PhantomData;
+use core::sync::atomic::Ordering;
+
+/// Pins used by a USART in synchronous mode
+pub trait Pins<USART> {}
+
+impl<USART, CK: CkPin<USART>, TX: TxPin<USART>, RX: RxPin<USART>> Pins<USART> for (CK, TX, RX) {}
+
+pub struct UsartSpi<USART, PINS> {
+    rb: *const pac::usart1::RegisterBlock,
+    pins: PINS,
+    _usart: PhantomData<USART>,
+}
+
+impl<USART: Instance, PINS: Pins<USART>> UsartSpi<USART, PINS> {
+    /// Configure and enable a USART as an SPI master
+    pub fn new(rcc: &mut rcc::Rcc, pins: PINS, config: Config) -> Result<Self, ConfigError> {
+        let clock = rcc.get_usart_clock_frequency(USART::NUMBER);
+        let brr = Oversampling::X16.brr(clock, config.baudrate)?;
+
+        Self::init(rcc, pins, config, |rb| unsafe {
+            rb.brr().write(|w| w.bits(brr));
+            rb.cr2().modify(|_, w| w.clken().set_bit().lbcl().set_bit());
+        })
+    }
+}
+
+impl<USART: FullFeatured, PINS: Pins<USART>> UsartSpi<USART, PINS> {
+    /// Configure and enable a USART as an SPI slave
+    ///
+    /// The slave is always selected, there is no slave select input.
+    pub fn new_slave(rcc: &mut rcc::Rcc, pins: PINS, config: Config) -> Result<Self, ConfigError> {
+        Self::init(rcc, pins, config, |rb| {
+            rb.cr2()
+                .modify(|_, w| w.slven().set_bit().dis_nss().set_bit());
+        })
+    }
+}
+
+impl<USART: Instance, PINS> UsartSpi<USART, PINS> {
+    fn init(
+        rcc: &mut rcc::Rcc,
+        pins: PINS,
+        config: Config,
+        mode: impl FnOnce(&pac::usart1::RegisterBlock),
+    ) -> Result<Self, ConfigError> {
+        if USART::taken().load(Ordering::Relaxed) {
+            return Err(ConfigError::Taken);
+        }
+        USART::taken().store(true, Ordering::Relaxed);
+
+        rcc.enable_peripheral_clock(USART::peripheral());
+
+        let spi = Self {
+            rb: USART::ptr(),
+            pins,
+            _usart: PhantomData,
+        };
+
+        unsafe {
+            let rb = &*spi.rb;
+
+            // 8 bits words, no parity, 1 stop bit
+            rb.cr1().reset();
+            rb.cr2().write(|w| {
+                w.cpol()
+                    .bit(config.polarity == Polarity::IdleHigh)
+                    .cpha()
+                    .bit(config.phase == Phase::CaptureOnSecondTransition)
+                    .msbfirst()
+                    .bit(config.bit_order == BitOrder::MsbFirst)
+            });
+            rb.cr3().reset();
+            mode(rb);
+            rb.cr1()
+                .modify(|_, w| w.te().set_bit().re().set_bit().ue().set_bit());
+        }
+
+        Ok(spi)
+    }
+
+    /// Disable the USART and release its pins
+    pub fn release(self, rcc: &mut rcc::Rcc) -> PINS {
+        unsafe {
+            (*self.rb).cr1().reset();
+            (*self.rb).cr2().reset();
+            (*self.rb).cr3().reset();
+        }
+        rcc.disable_peripheral_clock(USART::peripheral());
+        USART::taken().store(false, Ordering::Relaxed);
+
+        self.pins
+    }
+
+    /// Transmit a word and return the word received at the same time
+    pub fn transfer_word(&mut self, word: u8) -> Result<u8, Error> {
+        unsafe {
+            while (*self.rb).isr().read().bits() & TXFNF == 0 {}
+            (*self.rb).tdr().write(|w| w.tdr().bits(word as u16));
+
+            loop {
+                let isr = (*self.rb).isr().read().bits();
+
+                if isr & ORE != 0 {
+                    (*self.rb).icr().write(|w| w.bits(PE | FE | NE | ORE));
+                    return Err(Error::Overrun);
+                }
+
+                if isr & RXFNE != 0 {
+                    return Ok((*self.rb).rdr().read().rdr().bits() as u8);
+                }
+            }
+        }
+    }
+
+    /// Transmit and receive words, in place
+    pub fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Error> {
+        for word in words.iter_mut() {
+            *word = self.transfer_word(*word)?;
+        }
+
+        Ok(())
+    }
+
+    /// Wait until the last word is completely shifted out
+    pub fn flush(&mut self) {
+        unsafe { while (*self.rb).isr().read().bits() & TC == 0 {} }
+    }
+}
+
+/// USART synchronous mode configuration
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// Clock frequency in master mode
+    pub baudrate: u32,
+    pub polarity: Polarity,
+    pub phase: Phase,
+    pub bit_order: BitOrder,
+}
+
+impl Default for Config {
+    /// 1 MHz, SPI mode 0, MSB first
+    fn default() -> Self {
+        Self {
+            baudrate: 1_000_000,
+            polarity: Polarity::IdleLow,
+            phase: Phase::CaptureOnFirstTransition,
+            bit_order: BitOrder::MsbFirst,
+        }
+    }
+}
+
+/// Clock polarity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    /// Clock low when idle
+    IdleLow,
+    /// Clock high when idle
+    IdleHigh,
+}
+
+/// Clock phase
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Data captured on the first clock transition
+    CaptureOnFirstTransition,
+    /// Data captured on the second clock transition
+    CaptureOnSecondTransition,
+}
+
+/// Bit transmission order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Most significant bit first
+    MsbFirst,
+    /// Least significant bit first
+    LsbFirst,
+}
+
+#[cfg(feature = "embedded-hal")]
+impl embedded_hal::spi::Error for Error {
+    fn kind(&self) -> embedded_hal::spi::ErrorKind {
+        match self {
+            Error::Overrun => embedded_hal::spi::ErrorKind::Overrun,
+            _ => embedded_hal::spi::ErrorKind::Other,
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<USART: Instance, PINS> embedded_hal::spi::ErrorType for UsartSpi<USART, PINS> {
+    type Error = Error;
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<USART: Instance, PINS> embedded_hal::spi::SpiBus for UsartSpi<USART, PINS> {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        for word in words.iter_mut() {
+            *word = self.transfer_word(0)?;
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        for &word in words {
+            self.transfer_word(word)?;
+        }
+
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        for i in 0..read.len().max(write.len()) {
+            let word = self.transfer_word(write.get(i).copied().unwrap_or(0))?;
+            if let Some(read) = read.get_mut(i) {
+                *read = word;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        Self::transfer_in_place(self, words)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Self::flush(self);
+        Ok(())
+    }
+}
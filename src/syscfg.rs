@@ -0,0 +1,235 @@
+use crate::{pac, rcc};
+use core::convert::From;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+// SRAM parity error flag, cleared by writing 1
+const SRAM_PEF: u32 = 1 << 8;
+
+pub struct Syscfg {
+    rb: *const pac::syscfg::RegisterBlock,
+}
+
+impl Syscfg {
+    pub fn new(rcc: &mut rcc::Rcc) -> Option<Self> {
+        // Enable the SYSCFG peripheral clock
+        rcc.enable_peripheral_clock(rcc::Peripheral::APB2(rcc::APB2Peripheral::SYSCFG));
+
+        if TAKEN.load(Ordering::Relaxed) {
+            None
+        } else {
+            TAKEN.store(true, Ordering::Relaxed);
+
+            Some(Self {
+                rb: pac::SYSCFG::ptr(),
+            })
+        }
+    }
+
+    /// Get the memory mapped at address 0, selected by the boot mode
+    pub fn get_boot_mode(&mut self) -> BootMode {
+        unsafe { BootMode::from_u8((*self.rb).cfgr1().read().mem_mode().bits()) }
+    }
+
+    /// Remap PA11 onto the PA9 pin
+    pub fn set_pa11_remap(&mut self, state: bool) {
+        unsafe {
+            (*self.rb).cfgr1().modify(|_, w| w.pa11_rmp().bit(state));
+        }
+    }
+
+    /// Remap PA12 onto the PA10 pin
+    pub fn set_pa12_remap(&mut self, state: bool) {
+        unsafe {
+            (*self.rb).cfgr1().modify(|_, w| w.pa12_rmp().bit(state));
+        }
+    }
+
+    /// Set the IR modulation envelope signal source
+    pub fn set_ir_modulation(&mut self, source: IrModulation) {
+        unsafe {
+            (*self.rb)
+                .cfgr1()
+                .modify(|_, w| w.ir_mod().bits(source.into()));
+        }
+    }
+
+    /// Set the IR output polarity (`true` for inverted)
+    pub fn set_ir_polarity_inverted(&mut self, state: bool) {
+        unsafe {
+            (*self.rb).cfgr1().modify(|_, w| w.ir_pol().bit(state));
+        }
+    }
+
+    /// Set the I/O analog switch voltage booster state
+    ///
+    /// Improves the analog switches performance when VDDA is low.
+    pub fn set_analog_switch_booster(&mut self, state: bool) {
+        unsafe {
+            (*self.rb).cfgr1().modify(|_, w| w.boosten().bit(state));
+        }
+    }
+
+    /// Set an I2C fast-mode plus (20 mA) driver state
+    pub fn set_fast_mode_plus(&mut self, driver: FastModePlus, state: bool) {
+        let bit = 1u32 << u8::from(driver);
+
+        unsafe {
+            (*self.rb).cfgr1().modify(|r, w| {
+                if state {
+                    w.bits(r.bits() | bit)
+                } else {
+                    w.bits(r.bits() & !bit)
+                }
+            });
+        }
+    }
+
+    /// Disable the UCPD1 dead battery pull-down resistors on CC1 and CC2
+    #[cfg(any(
+        feature = "stm32g071",
+        feature = "stm32g081",
+        feature = "stm32g0b1",
+        feature = "stm32g0c1"
+    ))]
+    pub fn disable_ucpd1_dead_battery(&mut self) {
+        unsafe {
+            (*self.rb).cfgr1().modify(|_, w| w.ucpd1_strobe().set_bit());
+        }
+    }
+
+    /// Disable the UCPD2 dead battery pull-down resistors on CC1 and CC2
+    #[cfg(any(
+        feature = "stm32g071",
+        feature = "stm32g081",
+        feature = "stm32g0b1",
+        feature = "stm32g0c1"
+    ))]
+    pub fn disable_ucpd2_dead_battery(&mut self) {
+        unsafe {
+            (*self.rb).cfgr1().modify(|_, w| w.ucpd2_strobe().set_bit());
+        }
+    }
+
+    /// Connect the Cortex-M0+ lockup output to the timers break inputs
+    ///
+    /// Can only be cleared by a reset.
+    pub fn enable_lockup_break(&mut self) {
+        unsafe {
+            (*self.rb)
+                .cfgr2()
+                .modify(|r, w| w.bits(r.bits() & !SRAM_PEF).lockup_lock().set_bit());
+        }
+    }
+
+    /// Connect the SRAM parity error to the timers break inputs
+    ///
+    /// Can only be cleared by a reset.
+    pub fn enable_sram_parity_break(&mut self) {
+        unsafe {
+            (*self.rb)
+                .cfgr2()
+                .modify(|r, w| w.bits(r.bits() & !SRAM_PEF).sram_parity_lock().set_bit());
+        }
+    }
+
+    /// Check if an SRAM parity error was detected
+    pub fn is_sram_parity_error(&mut self) -> bool {
+        unsafe { (*self.rb).cfgr2().read().sram_pef().bit_is_set() }
+    }
+
+    /// Clear the SRAM parity error flag
+    pub fn clear_sram_parity_error(&mut self) {
+        unsafe {
+            // The other bits are lock bits, unaffected by writing 0
+            (*self.rb).cfgr2().write(|w| w.sram_pef().set_bit());
+        }
+    }
+}
+
+/// Memory mapped at address 0
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BootMode {
+    /// Main flash memory
+    MainFlash,
+    /// System flash memory (bootloader)
+    SystemFlash,
+    /// Embedded SRAM
+    Sram,
+}
+
+impl BootMode {
+    pub fn from_u8(value: u8) -> Self {
+        use BootMode::*;
+        match value & 3 {
+            1 => SystemFlash,
+            3 => Sram,
+            _ => MainFlash,
+        }
+    }
+}
+
+/// IR modulation envelope signal source
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrModulation {
+    /// TIM16
+    Tim16,
+    /// USART1
+    Usart1,
+    /// USART4
+    Usart4,
+}
+
+impl From<IrModulation> for u8 {
+    fn from(value: IrModulation) -> Self {
+        use IrModulation::*;
+        match value {
+            Tim16 => 0,
+            Usart1 => 1,
+            Usart4 => 2,
+        }
+    }
+}
+
+/// I2C fast-mode plus drivers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FastModePlus {
+    /// PB6 pin
+    PB6,
+    /// PB7 pin
+    PB7,
+    /// PB8 pin
+    PB8,
+    /// PB9 pin
+    PB9,
+    /// Pins used by I2C1
+    I2C1,
+    /// Pins used by I2C2
+    I2C2,
+    /// PA9 pin
+    PA9,
+    /// PA10 pin
+    PA10,
+    /// Pins used by I2C3
+    #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+    I2C3,
+}
+
+impl From<FastModePlus> for u8 {
+    fn from(value: FastModePlus) -> Self {
+        use FastModePlus::*;
+        match value {
+            PB6 => 16,
+            PB7 => 17,
+            PB8 => 18,
+            PB9 => 19,
+            I2C1 => 20,
+            I2C2 => 21,
+            PA9 => 22,
+            PA10 => 23,
+            #[cfg(any(feature = "stm32g0b1", feature = "stm32g0c1"))]
+            I2C3 => 24,
+        }
+    }
+}